@@ -2,16 +2,252 @@
 #![doc = include_str!("./README.md")]
 
 // internal
-pub use newer_type_macro::__implement_internal;
+pub use newer_type_macro::{__implement_bundle, __implement_internal};
+
+#[doc(hidden)]
+pub mod __private {
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    #[cfg(feature = "std")]
+    pub use std::boxed::Box;
+
+    pub use rustversion;
+
+    /// Sum type returned from the methods with return-position `impl Trait`
+    /// implemented for enums. More than two variants are represented by nesting
+    /// in `Right`.
+    pub enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    impl<L: Iterator, R: Iterator<Item = L::Item>> Iterator for Either<L, R> {
+        type Item = L::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match self {
+                Either::Left(l) => l.next(),
+                Either::Right(r) => r.next(),
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            match self {
+                Either::Left(l) => l.size_hint(),
+                Either::Right(r) => r.size_hint(),
+            }
+        }
+    }
+
+    impl<L: DoubleEndedIterator, R: DoubleEndedIterator<Item = L::Item>> DoubleEndedIterator
+        for Either<L, R>
+    {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            match self {
+                Either::Left(l) => l.next_back(),
+                Either::Right(r) => r.next_back(),
+            }
+        }
+    }
+
+    impl<L: ExactSizeIterator, R: ExactSizeIterator<Item = L::Item>> ExactSizeIterator
+        for Either<L, R>
+    {
+    }
+
+    impl<L: core::iter::FusedIterator, R: core::iter::FusedIterator<Item = L::Item>>
+        core::iter::FusedIterator for Either<L, R>
+    {
+    }
+
+    impl<L: Future, R: Future<Output = L::Output>> Future for Either<L, R> {
+        type Output = L::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            // SAFETY: the fields are never moved out of the pinned enum.
+            unsafe {
+                match self.get_unchecked_mut() {
+                    Either::Left(l) => Pin::new_unchecked(l).poll(cx),
+                    Either::Right(r) => Pin::new_unchecked(r).poll(cx),
+                }
+            }
+        }
+    }
+}
+
+// Passes the tokens through only if the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_std {
+    ($($t:tt)*) => { $($t)* };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_std {
+    ($($t:tt)*) => {};
+}
+
+/// Define the repeater trait used by [`target`] by default. Place this at the
+/// crate root of the crates defining target traits which refer to the types by
+/// relative paths.
+///
+/// The repeater carries such types to the crates using the target traits. It
+/// is not needed if the target traits refer to the types only by absolute
+/// paths, like `::core::primitive::usize`.
+///
+/// Two repeaters are defined: the public one for `pub` target traits, and the
+/// crate-visible one for the others, which can carry `pub(crate)` types.
+/// Without this macro, such target traits fail to compile with the error
+/// ``no `call_newer_type_repeater_macro_at_the_crate_root` in the root``.
+///
+/// # Example
+///
+/// ```
+/// newer_type::repeater!();
+///
+/// mod shapes {
+///     use newer_type::target;
+///
+///     pub struct Length(pub f64);
+///
+///     #[target]
+///     pub trait Area {
+///         fn area(&self) -> Length;
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! repeater {
+    () => {
+        #[doc(hidden)]
+        pub trait __NewerTypeRepeater<
+            const TRAIT_ID: ::core::primitive::u64,
+            const NTH: ::core::primitive::usize,
+            T: ?::core::marker::Sized,
+        >
+        {
+            type Type: ?::core::marker::Sized;
+        }
+
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub(crate) trait __NewerTypeCrateRepeater<
+            const TRAIT_ID: ::core::primitive::u64,
+            const NTH: ::core::primitive::usize,
+            T: ?::core::marker::Sized,
+        >
+        {
+            type Type: ?::core::marker::Sized;
+        }
+
+        // Imported by `#[target]` to report the missing repeater with its name.
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        pub enum call_newer_type_repeater_macro_at_the_crate_root {}
+    };
+}
 
 /// Implement a trait for given enum or struct. The trait should be defined with
 /// [`target`] attribute.
 ///
+/// # Arguments
+///
+/// The arguments are a comma-separated list of traits to implement, which can
+/// be mixed with the following options. A trait prefixed with `ref` or `mut` is
+/// implemented for `&Wrapper` or `&mut Wrapper` instead, delegating to `&Inner`
+/// or `&mut Inner`. Such traits can only have methods taking `Self` by value,
+/// like `ref IntoIterator`.
+/// Traits can also be conditionally implemented with `#[cfg(...)]`, like
+/// `#[implement(#[cfg(feature = "serde")] Serialize)]`.
+/// A foreign trait not declared with [`target`] can be implemented by
+/// declaring its items inline after `extern`, like
+/// `#[implement(extern ::other::Trait { fn method(&self) -> u32; })]`. The
+/// types in the items are resolved where the attribute is written, and the
+/// items are checked against the real trait by rustc. Methods with default
+/// implementations can be left out.
+///
+/// - `newtype` ... Generate `new()`, `into_inner()`, `as_inner()` and
+///   `as_inner_mut()` methods with the visibility of the struct, and [`From`]
+///   conversions between the struct and the inner type. The conversion into
+///   the inner type is omitted if the inner type is a type parameter (or a
+///   reference, `Box` or `Pin` of it), which the orphan rule rejects. Fields
+///   other than the inner field are initialized with [`Default`]. If the inner
+///   field is less visible than the struct, the items exposing it follow the
+///   same rule as [`Newtype`]: the methods except `new()` get the visibility
+///   of the field, and the conversion into the inner type is omitted. The
+///   inner field is selected as the trait implementations do, so [`Newtype`]
+///   is implemented for the struct even if it has multiple fields.
+/// - `validate` ... Path to a function `fn(&Inner) -> bool`. If specified,
+///   `new()` function returning [`ValidationError`] and [`TryFrom`]
+///   implementation are generated for the struct, and the inner value is
+///   checked again after it is mutated by the delegated methods. Combined
+///   with `newtype`, `as_inner_mut()` and `From<Inner>` are not generated.
+///   [`TryFrom`] is not implemented if the inner type is a type parameter (or
+///   a reference, `Box` or `Pin` of it), as it conflicts with the blanket
+///   implementation in `core`.
+/// - `on_invalid` ... What to do when a delegated method breaks the invariant.
+///   One of `"panic"` (default), `"debug_assert"` and `"rollback"`. With
+///   `"rollback"`, the inner value should implement [`Clone`] and is restored
+///   to the value before the method call.
+/// - `transparent` ... Add `#[repr(transparent)]` to the struct (or verify
+///   that it is already present) and generate `from_ref()`, `from_mut()`,
+///   `from_slice()` and `from_slice_mut()` casting the inner type to the
+///   struct without copying. `from_boxed()` and `into_boxed_inner()` are also
+///   generated with `std` feature. The inner type may be dynamically sized,
+///   e.g. `struct Name(str)`, in which case the slice casts are omitted.
+///   Cannot be combined with `validate`.
+/// - `with_supertraits` ... Also implement the supertraits of the traits in the
+///   same attribute, recursively. Only the supertraits declared in the same
+///   module as the trait (or as its `alternative`) are implemented, resolved by
+///   replacing the last segment of the trait path, which therefore must name
+///   the module, like `shapes::Area`. The other supertraits, like
+///   [`Debug`](core::fmt::Debug) for an error trait, are skipped, as well as the
+///   traits listed explicitly.
+/// - `inline` ... Inlining hint of the generated methods for the traits in the
+///   same attribute. One of `"always"`, `"never"` and `"default"` (no hint).
+///   Overrides the `inline` argument of [`target`]. Defaults to `#[inline]`.
+/// - `assert_impl` ... Assert that the inner fields implement the traits in the
+///   same attribute, reporting the missing implementation with the field
+///   responsible for it. Fields whose types mention the type or const
+///   parameters of the type are not checked, since the trait is implemented
+///   only when they implement it. Requires Rust 1.78 or later for
+///   `#[diagnostic::on_unimplemented]`.
+/// - `debug` ... Write the generated implementations of the traits in the same
+///   attribute, formatted, to `$CARGO_TARGET_DIR/newer-type/<crate>/` if the
+///   variable is set, or to `target/newer-type/<crate>/` of the workspace
+///   otherwise. The files are named after the type and the full path of the
+///   trait, like `Pet-animals.Named.rs`. A warning pointing at the file is
+///   emitted. Setting `NEWER_TYPE_DEBUG` environment
+///   variable to a comma-separated list of the type names (or `*`) has the
+///   same effect without editing the code; rebuild the crate after changing
+///   it, since the variable is not tracked by cargo.
+/// - `newer_type` ... Set path to `newer_type` crate, which is used by the
+///   items generated with the options above. Defaults to `::newer_type`.
+///
+/// The inner field may be dynamically sized, like `str`, `[T]` or `dyn Trait`.
+/// In that case, trait methods bounded by `where Self: Sized` are omitted from
+/// the implementation, and methods taking `Self` by value are rejected.
+///
+/// The traits should be defined with [`target`] or declared with `extern`,
+/// otherwise rustc reports that the macro named after the trait cannot be
+/// found, together with a warning to add [`target`] to the trait. The warning
+/// is not given for the traits with generic arguments, like `Scale<u32>`, and
+/// also appears for the target traits defined with newer-type 0.1. The traits
+/// in the standard
+/// library cannot be used directly; use the counterparts in `newer_type_std`,
+/// like `newer_type_std::fmt::Display` for `std::fmt::Display`.
+///
 /// # Example
 ///
 /// ```ignore
 /// use newer_type::implement;
-/// use newer_type_std::{ops::Extend, cmp::PartialEq};
+/// use newer_type_std::{ops::{AddAssign, Extend}, cmp::PartialEq};
 ///
 /// #[implement(Extend<usize>)]
 /// struct Example1(Vec<usize>);
@@ -24,53 +260,210 @@ pub use newer_type_macro::__implement_internal;
 ///
 /// #[implement(for<T: std::fmt::Debug> PartialEq<T>)]
 /// struct Example4<U>(U);
+///
+/// fn is_even(n: &usize) -> bool {
+///     n % 2 == 0
+/// }
+///
+/// #[implement(AddAssign<usize>, validate = is_even, on_invalid = "rollback")]
+/// struct EvenNumber(usize);
+///
+/// #[implement(transparent)]
+/// struct Name(str);
+///
+/// let name: &Name = Name::from_ref("foo");
 /// ```
 pub use newer_type_macro::implement;
 
+/// Derive macro version of [`implement`], which reads `#[implement(...)]` on
+/// the item and the fields as helper attributes.
+///
+/// The arguments are the same as [`implement`]. Unlike the attribute macro, the
+/// item is left untouched and only the implementations are emitted, so it
+/// works well with the other attribute macros and the IDEs. `transparent`
+/// requires `#[repr(transparent)]` written explicitly.
+///
+/// The helper attribute conflicts with [`implement`] imported in the same
+/// scope, so refer to the derive macro by the path without importing
+/// [`implement`].
+///
+/// # Example
+///
+/// ```
+/// use newer_type::target;
+///
+/// #[target]
+/// trait Describe {
+///     fn describe(&self) -> ::std::string::String;
+/// }
+///
+/// impl Describe for ::core::primitive::u32 {
+///     fn describe(&self) -> ::std::string::String {
+///         ::std::format!("{} items", self)
+///     }
+/// }
+///
+/// #[derive(newer_type::Implement, Debug)]
+/// struct Count {
+///     #[implement(Describe)]
+///     value: u32,
+///     label: &'static str,
+/// }
+///
+/// let count = Count { value: 3, label: "apples" };
+/// assert_eq!(count.describe(), "3 items");
+/// ```
+pub use newer_type_macro::Implement;
+
+/// A wrapper type around the inner type.
+///
+/// This trait is implemented by [`implement`] macro for structs with single
+/// field and structs with `#[implement(newtype)]`, except validated ones. This
+/// can be used to write generic code over the newtypes.
+///
+/// The trait exposes the inner field to everyone who can see the struct, so
+/// it is not implemented if the inner field is less visible than the struct,
+/// like `pub struct Meters(f64);`. Make the field as visible as the struct,
+/// like `pub struct Meters(pub f64);`, to implement the trait.
+///
+/// # Example
+///
+/// ```
+/// use newer_type::{implement, Newtype};
+///
+/// #[implement]
+/// struct Meters(f64);
+///
+/// fn double<T: Newtype<Inner = f64>>(value: T) -> T {
+///     T::from_inner(value.into_inner() * 2.0)
+/// }
+///
+/// assert_eq!(double(Meters(1.5)).0, 3.0);
+/// ```
+pub trait Newtype {
+    /// The type of the wrapped value.
+    type Inner;
+
+    /// Wrap the inner value.
+    fn from_inner(inner: Self::Inner) -> Self;
+
+    /// Unwrap the inner value.
+    fn into_inner(self) -> Self::Inner;
+
+    /// Get a reference to the inner value.
+    fn inner(&self) -> &Self::Inner;
+
+    /// Get a mutable reference to the inner value.
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+}
+
+/// The error returned when the value is rejected by the function given to
+/// `validate` argument of [`implement`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ValidationError<T>(T);
+
+impl<T> ValidationError<T> {
+    /// Create an error with the rejected value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Get the rejected value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::fmt::Display for ValidationError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the value is rejected by validation")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug> std::error::Error for ValidationError<T> {}
+
 /// Define a trait for use of [`implement`] macro.
 ///
 /// # Arguments (all optional)
 ///
 /// - `alternative` ... Trait. If specified, implement this trait instead of the
 ///   target trait itself. The target trait is used only for an argument of
-///   [`implement`] macro.
+///   [`implement`] macro. The methods declared in the target trait are checked
+///   at compile time to be callable as the methods of the same name in this
+///   trait, so a mismatched signature is reported at the declared method.
 /// - `newer_type` ... Set path to `newer_type` crate. Defaults to
 ///   `::newer_type`. Example: `::your_crate::_export::newer_type`.
-/// - `repeater` ... Absolute path to the `Repeater` crate. see the example
+/// - `repeater` ... Absolute path to the `Repeater` trait. see the example
 ///   section. The `Repeater` trait is defined in the same crate that the target
 ///   trait is defined, and should be visible from the users, which refer to the
-///   trait with `#[implement]` macro.
+///   trait with `#[implement]` macro. Defaults to the trait defined by
+///   [`repeater!`] at the crate root.
+/// - `inline` ... Inlining hint of the generated methods. One of `"always"`,
+///   `"never"` and `"default"` (no hint). Defaults to `#[inline]`.
+/// - `complete` ... Flag, used with `alternative`. Reports an error if the
+///   target trait does not declare any provided method of the alternative
+///   trait which can be forwarded, which otherwise falls back to the default
+///   implementation silently. Supported only for the traits in `core` and
+///   `std` which `newer_type_std` provides, with a warning for the others.
+///   Unstable methods are not required. The provided methods are listed by
+///   hand as of Rust 1.83, so the methods stabilized later are not required.
+///
+/// Doc comments, `#[track_caller]` and lint attributes on the trait methods are
+/// copied to the generated methods. `#[cfg(...)]` on the trait items is also
+/// copied to the generated items, and so is `#[rustversion::since(...)]` and
+/// the other attributes of [`rustversion`](https://docs.rs/rustversion), which
+/// gate the items stabilized after the minimum supported Rust version. They are
+/// resolved through `newer_type`, so the implementing crates need not depend on
+/// `rustversion`.
+///
+/// The trait is also registered as a hidden macro under its name, with the
+/// visibility of the trait. Only for `pub` traits, the macro is exported with a
+/// hidden name at the crate root, as required by `macro_rules`.
+///
+/// The methods of the trait may be `async fn` or return `impl Trait`, which
+/// require Rust 1.75 or later. When such a trait is implemented for an enum,
+/// the values returned from the variants are wrapped into a sum type, which
+/// implements [`Iterator`], [`DoubleEndedIterator`], [`ExactSizeIterator`],
+/// [`FusedIterator`](core::iter::FusedIterator) and
+/// [`Future`](core::future::Future). Returning `impl Trait` of other traits
+/// from the enums is rejected.
+///
+/// The types in the signatures may be referred by relative paths, including
+/// `self::`, `super::` and `crate::` paths to `pub(crate)` types. They are
+/// carried to the implementations by the repeater. The trait bounds and the
+/// types depending on the generic parameters of the methods cannot be carried,
+/// so they should be referred by `crate::` or absolute paths, unless they are
+/// in the prelude.
 ///
 /// # Example
 ///
 /// ```
 /// use newer_type::target;
 ///
-/// pub trait Repeater<const TRAIT_ID : u64, const NTH : usize, T: ?Sized> {
-///     type Type;
-/// }
-///
-/// #[target(repeater = Repeater)]
+/// #[target]
 /// trait MyTrait {
 ///     fn my_fn(&self) -> ::core::primitive::usize;
 /// }
 /// ```
 ///
+/// Types referred by relative paths require [`repeater!`] at the crate root.
+///
 /// ```
 /// use newer_type::target;
 /// type TypeFromContext = usize;
 ///
-/// pub trait Repeater<const TRAIT_ID : u64, const NTH : usize, T: ?Sized> {
-///     type Type;
-/// }
+/// newer_type::repeater!();
 ///
-/// #[target(repeater = Repeater)]
+/// #[target]
 /// trait MyTrait {
 ///     fn my_fn(&self, t: TypeFromContext) -> Box<usize>;
 /// }
+/// # fn main() {}
 /// ```
 ///
-/// We recomend this pattern to set `repeater` path correctly.
+/// The repeater trait can be also given explicitly. We recomend this pattern to
+/// set `repeater` path correctly.
 ///
 /// ```ignore
 /// use newer_type::target;
@@ -92,3 +485,45 @@ pub use newer_type_macro::implement;
 /// emit_trait!();
 /// ```
 pub use newer_type_macro::target;
+
+/// Define a trait bundle, a name standing for a group of traits defined with
+/// [`target`]. Implementing the bundle with [`implement`] implements all the
+/// member traits.
+///
+/// The bundle is declared as a trait alias `trait Bundle = A + B;`, or as an
+/// empty trait with the member traits as the supertraits, like
+/// `trait Bundle: A + B {}`. The member traits should be specified with
+/// absolute paths, either `crate::...` or `::some_crate::...`. The trait alias
+/// is accepted on stable, though rustc warns that the syntax is experimental.
+/// The bundle is emitted as an empty trait, implemented for the types
+/// implementing all the member traits.
+/// The generic parameters of the bundle are substituted in the member traits,
+/// and omitted ones are left to the defaults of the member traits.
+///
+/// # Arguments (all optional)
+///
+/// - `newer_type` ... Set path to `newer_type` crate. Defaults to
+///   `::newer_type`.
+///
+/// # Example
+///
+/// ```ignore
+/// use newer_type::{implement, target, target_bundle};
+///
+/// #[target]
+/// pub trait Name {
+///     fn name(&self) -> ::std::string::String;
+/// }
+///
+/// #[target]
+/// pub trait Age {
+///     fn age(&self) -> ::core::primitive::u32;
+/// }
+///
+/// #[target_bundle]
+/// pub trait Profile = crate::Name + crate::Age;
+///
+/// #[implement(Profile)]
+/// struct Employee(Person);
+/// ```
+pub use newer_type_macro::target_bundle;
@@ -0,0 +1,291 @@
+use crate::diagnostics;
+use crate::implement::{Implementor, Output as ImplementOutput, SupertraitWalk};
+use crate::implement_internal::{generic_args_of, substitute_generic_args};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use syn::*;
+use template_quote::quote;
+
+pub struct Argument {
+    newer_type: Path,
+}
+
+impl syn::parse::Parse for Argument {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let mut newer_type = parse_quote!(::newer_type);
+
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            input.parse::<token::Eq>()?;
+            match ident.to_string().as_str() {
+                "newer_type" => {
+                    newer_type = input.parse()?;
+                }
+                _ => {
+                    return Err(Error::new_spanned(&ident, "Unsupported argument"));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Argument { newer_type })
+    }
+}
+
+/// Make the path resolvable from the crate using the bundle, by replacing
+/// leading `crate` with `$crate`. Returns `None` for relative paths.
+fn resolve_path(path: &Path) -> Option<TokenStream> {
+    if path.leading_colon.is_some() {
+        return Some(quote!(#path));
+    }
+    match path.segments.first() {
+        Some(seg) if seg.ident == "crate" => {
+            let rest = path.segments.iter().skip(1);
+            Some(quote!($crate #(for seg in rest) { :: #seg }))
+        }
+        _ => None,
+    }
+}
+
+/// Accept the bundle either as an empty trait `trait X: A + B {}` or as a
+/// trait alias `trait X = A + B;`, normalizing to the former.
+pub fn bundle_def(item: Item) -> ItemTrait {
+    match item {
+        Item::Trait(item_trait) => item_trait,
+        Item::TraitAlias(alias) => ItemTrait {
+            attrs: alias.attrs,
+            vis: alias.vis,
+            unsafety: None,
+            auto_token: None,
+            restriction: None,
+            trait_token: alias.trait_token,
+            ident: alias.ident,
+            generics: alias.generics,
+            colon_token: Some(Token![:](alias.eq_token.span)),
+            supertraits: alias.bounds,
+            brace_token: Default::default(),
+            items: Vec::new(),
+        },
+        item => abort!(
+            item,
+            "`#[target_bundle]` should be placed on a trait";
+            hint = "declare the bundle as `trait Bundle = A + B;` or `trait Bundle: A + B {}`"
+        ),
+    }
+}
+
+pub fn target_bundle(arg: Argument, input: ItemTrait) -> TokenStream {
+    if let Some(item) = input.items.first() {
+        abort!(
+            item,
+            "The trait bundle should not have items";
+            note = "the member traits are given as the supertraits"
+        );
+    }
+    let nonce = crate::nonce("target_bundle", &input);
+    let mut members = Vec::new();
+    for bound in &input.supertraits {
+        if let TypeParamBound::Trait(TraitBound { path, .. }) = bound {
+            diagnostics::check_target_path(path);
+        }
+        match bound {
+            TypeParamBound::Trait(TraitBound {
+                modifier: TraitBoundModifier::None,
+                lifetimes: None,
+                path,
+                ..
+            }) => members.push(resolve_path(path).unwrap_or_else(|| {
+                abort!(
+                    path,
+                    "The member of the trait bundle should be specified with absolute path";
+                    hint = "use `crate::path::to::Trait` or `::some_crate::Trait`"
+                )
+            })),
+            _ => abort!(
+                bound,
+                "Only target traits are allowed as the members of the trait bundle"
+            ),
+        }
+    }
+    if let Some(where_clause) = &input.generics.where_clause {
+        abort!(
+            where_clause,
+            "where clause is not supported on trait bundles"
+        );
+    }
+    let newer_type = resolve_path(&arg.newer_type).unwrap_or_else(|| {
+        abort!(
+            &arg.newer_type,
+            "`newer_type` should be specified with absolute path"
+        )
+    });
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mac_name = Ident::new(&format!("__newer_type_bundle__{nonce}"), Span::call_site());
+    let registration = crate::emit_registration(
+        &input.vis,
+        &input.ident,
+        &mac_name,
+        quote! {
+            #newer_type::__implement_bundle! {
+                /* Implementor */ ($($t)*)
+                /* protocol */ version = #{crate::PROTOCOL_VERSION},
+                /* bundle_def */
+                trait #{&input.ident} #ty_generics:
+                    #(for (n, member) in members.iter().enumerate()) {
+                        #(if n > 0) { + } #member
+                    }
+                {}
+            }
+        },
+    );
+    quote! {
+        #registration
+        #{emit_bundle_trait(input)}
+    }
+}
+
+/// The bundle as a trait implemented for every type implementing the members,
+/// marked as a target for the check of `#[implement]`.
+fn emit_bundle_trait(mut input: ItemTrait) -> TokenStream {
+    input.items.push(diagnostics::marker_method(&input.ident));
+    let mut impl_generics = input.generics.clone();
+    for param in impl_generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Lifetime(_) => (),
+        }
+    }
+    impl_generics
+        .params
+        .push(parse_quote!(__NewerTypeT: ?::core::marker::Sized));
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    quote! {
+        #[allow(private_bounds)]
+        #input
+        impl #impl_generics #{&input.ident} #ty_generics for __NewerTypeT
+        where
+            __NewerTypeT: #{&input.supertraits}
+        {}
+    }
+}
+
+/// Drop the parameters given by `for<...>` which the member does not use, like
+/// `Rhs` for `Neg` in `for<Rhs> Arithmetic<Rhs>`, as the implementation
+/// cannot have unconstrained parameters. The predicates on them are dropped
+/// as well, and so is `for<...>` if no parameter is left.
+fn retain_used_generics(member: &mut Implementor) {
+    let Some((_, generics)) = &mut member.generics else {
+        return;
+    };
+    fn idents(tokens: TokenStream) -> Vec<Ident> {
+        let mut found = Vec::new();
+        for tt in tokens {
+            match tt {
+                TokenTree::Ident(ident) => found.push(ident),
+                TokenTree::Group(group) => found.extend(idents(group.stream())),
+                _ => (),
+            }
+        }
+        found
+    }
+    let path = &member.path;
+    let used = idents(quote!(#path));
+    let param_ident = |param: &GenericParam| match param {
+        GenericParam::Type(param) => param.ident.clone(),
+        GenericParam::Lifetime(param) => param.lifetime.ident.clone(),
+        GenericParam::Const(param) => param.ident.clone(),
+    };
+    let unused: Vec<Ident> = generics
+        .params
+        .iter()
+        .map(param_ident)
+        .filter(|ident| !used.contains(ident))
+        .collect();
+    generics.params = std::mem::take(&mut generics.params)
+        .into_pairs()
+        .filter(|pair| !unused.contains(&param_ident(pair.value())))
+        .collect();
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_pairs()
+            .filter(|pair| {
+                let pred = pair.value();
+                !idents(quote!(#pred))
+                    .iter()
+                    .any(|ident| unused.contains(ident))
+            })
+            .collect();
+    }
+    if generics.params.is_empty() {
+        member.generics = None;
+    }
+}
+
+pub struct Input {
+    output: ImplementOutput,
+    bundle_def: ItemTrait,
+}
+
+impl syn::parse::Parse for Input {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let inner_stream;
+        parenthesized!(inner_stream in input);
+        let output = inner_stream.parse()?;
+        crate::parse_protocol_version(input, "`#[target_bundle]` trait bundle")?;
+        let bundle_def = input.parse()?;
+        if input.is_empty() {
+            Ok(Self { output, bundle_def })
+        } else {
+            Err(input.error("Bad trailing tokens"))
+        }
+    }
+}
+
+impl Input {
+    pub fn implement_bundle(&self) -> TokenStream {
+        let implementor = &self.output.implementor;
+        let generic_args = generic_args_of(&self.bundle_def.generics, implementor);
+        let members = self
+            .bundle_def
+            .supertraits
+            .iter()
+            .filter_map(|bound| match bound {
+                TypeParamBound::Trait(TraitBound { path, .. }) => {
+                    let mut path = path.clone();
+                    if let Some(seg) = path.segments.last_mut() {
+                        substitute_generic_args(seg, &self.bundle_def.generics, &generic_args);
+                    }
+                    let mut member = implementor.clone();
+                    member.path = path;
+                    retain_used_generics(&mut member);
+                    Some(member)
+                }
+                _ => None,
+            });
+        let target_def = &self.output.target_def;
+        let options = self.output.options();
+        if let Some(mut walk) = self.output.supertraits.clone() {
+            // Continue the walk with the members queued in place of the bundle.
+            walk.done.push(SupertraitWalk::key(implementor));
+            for member in members {
+                if !walk.contains(&member) {
+                    walk.queue.push(member);
+                }
+            }
+            walk.emit_next(target_def, options)
+        } else {
+            members
+                .map(|member| member.emit_impl(target_def, options, None))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::implement::{Adt, Implementor};
+use proc_macro2::TokenStream;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use template_quote::quote;
+
+/// Returns `true` if the ADT is listed in `NEWER_TYPE_DEBUG` environment
+/// variable, which is a comma-separated list of the type names or `*`.
+pub fn is_enabled_by_env(adt: &Adt) -> bool {
+    let Ok(names) = std::env::var("NEWER_TYPE_DEBUG") else {
+        return false;
+    };
+    let ident = adt.ident();
+    names
+        .split(',')
+        .map(str::trim)
+        .any(|name| name == "*" || ident == name)
+}
+
+/// The directory to write the expansions, which is `$CARGO_TARGET_DIR/newer-type`
+/// if the variable is set, or `target/newer-type` relative to the directory
+/// where rustc is invoked, i.e. the workspace root for cargo.
+fn output_dir() -> PathBuf {
+    let base = std::env::var_os("CARGO_TARGET_DIR").map_or_else(
+        || std::env::current_dir().unwrap_or_default().join("target"),
+        PathBuf::from,
+    );
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    base.join("newer-type").join(crate_name)
+}
+
+/// The file name of the expansion, which consists of the ADT and the full path
+/// of the trait, like `Pet-crate.animals.Named.rs`. The characters not allowed
+/// in file names on some platforms are replaced with `_`.
+fn file_name(adt: &Adt, implementor: &Implementor) -> String {
+    let path = &implementor.path;
+    let path = quote!(#path)
+        .to_string()
+        .replace(' ', "")
+        .replace("::", ".");
+    let mut trait_name = String::new();
+    for c in path.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            trait_name.push(c);
+        } else if !trait_name.ends_with('_') {
+            trait_name.push('_');
+        }
+    }
+    let ref_kind = implementor
+        .ref_kind
+        .as_ref()
+        .map(|kind| format!("{}-", quote!(#kind)))
+        .unwrap_or_default();
+    format!(
+        "{}-{}{}.rs",
+        adt.ident(),
+        ref_kind,
+        trait_name.trim_matches(|c| c == '_' || c == '.')
+    )
+}
+
+/// Write the expansion formatted with `prettyplease` to a file, and emit a
+/// warning pointing at the file on the implementor.
+pub fn dump(adt: &Adt, implementor: &Implementor, tokens: &TokenStream) -> TokenStream {
+    // The expansion is left unformatted in the unlikely case it is not a
+    // valid file, so that it can still be inspected.
+    let formatted = syn::parse2(tokens.clone())
+        .map_or_else(|_| tokens.to_string(), |file| prettyplease::unparse(&file));
+    let code = format!(
+        "// Expansion of `#[implement({})]` for `{}`\n\n{}",
+        implementor,
+        adt.ident(),
+        formatted
+    );
+    let dir = output_dir();
+    let path = dir.join(file_name(adt, implementor));
+    let message = match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, code)) {
+        Ok(_) => format!("newer_type: the expansion is written to {}", path.display()),
+        Err(e) => format!(
+            "newer_type: failed to write the expansion to {}: {e}",
+            path.display()
+        ),
+    };
+    crate::diagnostics::warning(implementor.path.span(), &message)
+}
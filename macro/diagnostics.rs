@@ -0,0 +1,376 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort;
+use syn::spanned::Spanned;
+use syn::*;
+use template_quote::{quote, quote_spanned};
+
+/// Emit a warning with `message` at `span`. Stable proc macros cannot emit
+/// warnings, so use a deprecated item.
+pub fn warning(span: Span, message: &str) -> TokenStream {
+    let span = span.resolved_at(Span::call_site());
+    quote_spanned! {span =>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const newer_type_warning: () = ();
+            newer_type_warning
+        };
+    }
+}
+
+/// Traits provided by `newer_type_std`, listed by the module, to suggest them
+/// in place of the traits of `std`.
+const STD_TARGETS: &[(&str, &[&str])] = &[
+    ("alloc", &["GlobalAlloc"]),
+    ("borrow", &["Borrow", "BorrowMut", "ToOwned"]),
+    ("cmp", &["PartialEq", "Eq", "PartialOrd", "Ord"]),
+    ("convert", &["AsRef", "AsMut", "Into", "TryInto"]),
+    ("error", &["Error"]),
+    (
+        "fmt",
+        &[
+            "Display", "Debug", "Binary", "Octal", "LowerHex", "UpperHex", "LowerExp", "UpperExp",
+            "Pointer", "Write",
+        ],
+    ),
+    ("future", &["Future", "IntoFuture"]),
+    ("hash", &["Hash", "Hasher", "BuildHasher"]),
+    ("io", &["Read", "Write", "BufRead", "Seek"]),
+    (
+        "iter",
+        &[
+            "IntoIterator",
+            "Extend",
+            "Iterator",
+            "FusedIterator",
+            "ExactSizeIterator",
+            "DoubleEndedIterator",
+        ],
+    ),
+    ("net", &["ToSocketAddrs"]),
+    (
+        "ops",
+        &[
+            "Deref",
+            "DerefMut",
+            "Index",
+            "IndexMut",
+            "Not",
+            "BitAnd",
+            "BitOr",
+            "BitXor",
+            "Shl",
+            "Shr",
+            "BitAndAssign",
+            "BitOrAssign",
+            "BitXorAssign",
+            "ShlAssign",
+            "ShrAssign",
+            "Add",
+            "Sub",
+            "Mul",
+            "Div",
+            "Rem",
+            "Neg",
+            "AddAssign",
+            "SubAssign",
+            "MulAssign",
+            "DivAssign",
+            "RemAssign",
+        ],
+    ),
+    ("process", &["Termination"]),
+    ("string", &["ToString"]),
+    ("task", &["Wake"]),
+    (
+        "bundles",
+        &[
+            "Arithmetic",
+            "Bitwise",
+            "Formatting",
+            "Comparison",
+            "IteratorFull",
+            "IoStream",
+        ],
+    ),
+];
+
+/// Provided methods of the traits in `core` and `std`, which can be forwarded
+/// to the inner type, listed by the module and the trait. The methods
+/// mentioning `Self` other than the receiver, its associated types and `Self:
+/// Sized` (e.g. `Iterator::map`) are not listed, as well as the methods
+/// bounded by `Try` (e.g. `Iterator::try_fold`), which is unstable to name in
+/// the forwarding signature, and the unstable methods.
+///
+/// The list is maintained by hand, and is up to date as of Rust 1.83. The
+/// listed methods are required to be declared, and the declared methods are
+/// verified to exist by the check of the alternative. The methods stabilized
+/// later are detected by `tests/provided_methods.rs`, which compares the list
+/// with the sources of the toolchain.
+const PROVIDED_METHODS: &[(&str, &str, &[&str])] = &[
+    ("cmp", "PartialEq", &["ne"]),
+    ("cmp", "PartialOrd", &["lt", "le", "gt", "ge"]),
+    ("cmp", "Ord", &[]),
+    ("error", "Error", &["source", "description", "cause"]),
+    ("fmt", "Write", &["write_char", "write_fmt"]),
+    ("hash", "BuildHasher", &["hash_one"]),
+    (
+        "hash",
+        "Hasher",
+        &[
+            "write_u8",
+            "write_u16",
+            "write_u32",
+            "write_u64",
+            "write_u128",
+            "write_usize",
+            "write_i8",
+            "write_i16",
+            "write_i32",
+            "write_i64",
+            "write_i128",
+            "write_isize",
+        ],
+    ),
+    (
+        "io",
+        "Read",
+        &[
+            "read_vectored",
+            "read_to_end",
+            "read_to_string",
+            "read_exact",
+        ],
+    ),
+    ("io", "Write", &["write_vectored", "write_all", "write_fmt"]),
+    ("io", "BufRead", &["read_until", "skip_until", "read_line"]),
+    (
+        "io",
+        "Seek",
+        &["rewind", "stream_position", "seek_relative"],
+    ),
+    (
+        "iter",
+        "Iterator",
+        &[
+            "size_hint",
+            "count",
+            "last",
+            "nth",
+            "for_each",
+            "collect",
+            "partition",
+            "fold",
+            "reduce",
+            "all",
+            "any",
+            "find",
+            "find_map",
+            "position",
+            "max",
+            "min",
+            "max_by_key",
+            "max_by",
+            "min_by_key",
+            "min_by",
+            "sum",
+            "product",
+            "cmp",
+            "partial_cmp",
+            "eq",
+            "ne",
+            "lt",
+            "le",
+            "gt",
+            "ge",
+            "is_sorted",
+            "is_sorted_by",
+            "is_sorted_by_key",
+        ],
+    ),
+    (
+        "iter",
+        "DoubleEndedIterator",
+        &["nth_back", "rfold", "rfind"],
+    ),
+    ("iter", "ExactSizeIterator", &["len"]),
+    ("iter", "FusedIterator", &[]),
+];
+
+fn std_target(module: &str, name: &str) -> bool {
+    STD_TARGETS
+        .iter()
+        .any(|(m, traits)| *m == module && traits.contains(&name))
+}
+
+/// Returns `false` if the crate being compiled is not given the dependency
+/// `name` by `--extern`. The arguments of rustc are seen from the proc macro,
+/// which runs in the compiler process; outside of rustc (e.g. in the proc
+/// macro server of an IDE), the dependency is assumed to be given.
+fn has_dependency(name: &str) -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|arg| arg == "--crate-name") {
+        return true;
+    }
+    args.windows(2).any(|pair| {
+        // `--extern [opts:]name[=path]`
+        let spec = pair[1].split('=').next().unwrap_or_default();
+        pair[0] == "--extern" && spec.rsplit(':').next() == Some(name)
+    })
+}
+
+/// The name of the marker method added to the trait `ident`, which is looked
+/// for by [`check_target_marker`]. The name of the trait is included, not to
+/// be ambiguous with the marker methods of the supertraits.
+fn marker_name(ident: &Ident, span: Span) -> Ident {
+    Ident::new(&format!("__newer_type_target_{}", ident), span)
+}
+
+/// The hidden method which marks the trait as declared with `#[target]` or
+/// `#[target_bundle]`.
+pub fn marker_method(ident: &Ident) -> TraitItem {
+    let marker = marker_name(ident, Span::call_site());
+    parse_quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        fn #marker() where Self: ::core::marker::Sized {}
+    }
+}
+
+/// Warn if `path` given to `#[implement]` is not a target trait. Such a trait
+/// is reported by rustc as a missing macro, which does not tell that the trait
+/// lacks `#[target]`.
+///
+/// The marker method of the target trait is looked for on a type parameter
+/// bounded by the trait, which is preferred over the deprecated fallback
+/// method in scope. The check is skipped for the paths with generic arguments
+/// or `for<...>`, since the bound of the check would require the where clause
+/// of the trait to hold on the type parameter.
+pub fn check_target_marker(path: &Path) -> TokenStream {
+    if path
+        .segments
+        .iter()
+        .any(|seg| !matches!(seg.arguments, PathArguments::None))
+    {
+        return TokenStream::new();
+    }
+    if matches!(path.segments.first(), Some(seg) if seg.ident == "newer_type_std")
+        && !has_dependency("newer_type_std")
+    {
+        return warning(
+            path.span(),
+            "newer_type: `newer_type_std` is not a dependency of this crate; add `newer-type-std` to `[dependencies]` in Cargo.toml",
+        );
+    }
+    let message = format!(
+        "`{}` is not a target trait; add `#[target]` to this trait",
+        quote!(#path).to_string().replace(' ', "")
+    );
+    let Some(last) = path.segments.last() else {
+        return TokenStream::new();
+    };
+    let span = path.span().resolved_at(Span::call_site());
+    let marker = marker_name(&last.ident, span);
+    quote_spanned! {span =>
+        const _: () = {
+            trait __NewerTypeNotTarget {
+                #[deprecated(note = #message)]
+                #[allow(non_snake_case)]
+                fn #marker() {}
+            }
+            impl<T: ?::core::marker::Sized> __NewerTypeNotTarget for T {}
+            #[allow(dead_code)]
+            fn __newer_type_check<__T: #path>() {
+                let _ = __T::#marker;
+            }
+        };
+    }
+}
+
+/// Detect the traits of `std`, `core` and `alloc` given to `#[implement]`,
+/// which would be otherwise reported as a missing macro by rustc. The other
+/// paths are checked by [`check_target_marker`].
+pub fn check_target_path(path: &Path) {
+    let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    let (Some(krate), Some(name)) = (segments.first(), segments.last()) else {
+        return;
+    };
+    if segments.len() < 2 {
+        return;
+    }
+    if !matches!(krate.as_str(), "std" | "core" | "alloc") {
+        return;
+    }
+    let module = segments[1..segments.len() - 1].join("::");
+    if std_target(&module, name) {
+        abort!(
+            path,
+            "`{}::{}::{}` is not a target trait", krate, module, name;
+            hint = "use `newer_type_std::{}::{}` instead{}", module, name,
+                if has_dependency("newer_type_std") {
+                    ""
+                } else {
+                    ", adding `newer-type-std` to `[dependencies]` in Cargo.toml"
+                }
+        )
+    } else {
+        abort!(
+            path,
+            "`{}` is not a target trait", segments.join("::");
+            note = "`newer_type_std` does not provide the counterpart of this trait";
+            hint = "declare its items inline like `extern {} {{ ... }}`, or use `#[derive]` if possible", segments.join("::")
+        )
+    }
+}
+
+/// Abort if the trait declared with `#[target(alternative = ..., complete)]`
+/// lacks any provided method of the alternative trait which can be forwarded.
+/// Returns a warning if the provided methods of the alternative are not known.
+pub fn check_complete(complete: &Ident, alternative: &Path, input: &ItemTrait) -> TokenStream {
+    let segments: Vec<String> = alternative
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect();
+    let provided = match (segments.first(), segments.last()) {
+        (Some(krate), Some(name))
+            if segments.len() >= 2 && matches!(krate.as_str(), "std" | "core" | "alloc") =>
+        {
+            let module = segments[1..segments.len() - 1].join("::");
+            PROVIDED_METHODS
+                .iter()
+                .find(|(m, t, _)| *m == module && t == name)
+                .map(|(_, _, methods)| *methods)
+        }
+        _ => None,
+    };
+    let Some(provided) = provided else {
+        return warning(
+            complete.span(),
+            &format!(
+                "newer_type: `complete` is not checked, as the provided methods of `{}` are not known",
+                segments.join("::")
+            ),
+        );
+    };
+    let missing: Vec<_> = provided
+        .iter()
+        .filter(|method| {
+            !input
+                .items
+                .iter()
+                .any(|item| matches!(item, TraitItem::Fn(f) if f.sig.ident == method))
+        })
+        .map(|method| format!("`{}`", method))
+        .collect();
+    if !missing.is_empty() {
+        abort!(
+            input.ident,
+            "`{}` does not declare the provided methods of `{}`: {}",
+            input.ident, segments.join("::"), missing.join(", ");
+            note = complete.span() => "`complete` requires every provided method which can be forwarded";
+            hint = "declare the methods in the trait"
+        );
+    }
+    TokenStream::new()
+}
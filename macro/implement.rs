@@ -1,27 +1,58 @@
+use crate::diagnostics;
+use crate::implement_internal;
+use crate::newtype;
 use crate::ResultExt;
-use derive_syn_parse::Parse;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::abort;
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::*;
 use template_quote::{quote, ToTokens};
 
 pub struct Output {
     pub implementor: Implementor,
     pub target_def: Adt,
+    pub inline: Option<Inline>,
+    pub assert_impl: bool,
+    pub debug: bool,
+    pub supertraits: Option<SupertraitWalk>,
+}
+
+impl Output {
+    pub fn options(&self) -> ImplementorOptions {
+        ImplementorOptions {
+            inline: self.inline,
+            with_supertraits: self.supertraits.is_some(),
+            assert_impl: self.assert_impl,
+            debug: self.debug,
+        }
+    }
 }
 
 impl syn::parse::Parse for Output {
     fn parse(input: parse::ParseStream) -> Result<Self> {
+        crate::parse_protocol_version(input, "`#[implement]`")?;
         let inner_stream;
         parenthesized!(inner_stream in input);
         let implementor = inner_stream.parse()?;
         let target_def = input.parse()?;
         let _ = input.parse::<Token![,]>();
+        let inline = Inline::parse_option(input)?;
+        let _ = input.parse::<Token![,]>();
+        let assert_impl = parse_flag(input, "assert_impl").is_some();
+        let _ = input.parse::<Token![,]>();
+        let debug = parse_flag(input, "debug").is_some();
+        let _ = input.parse::<Token![,]>();
+        let supertraits = SupertraitWalk::parse_option(input)?;
+        let _ = input.parse::<Token![,]>();
         if input.is_empty() {
             Ok(Self {
                 implementor,
                 target_def,
+                inline,
+                assert_impl,
+                debug,
+                supertraits,
             })
         } else {
             Err(input.error("Bad trailing tokens"))
@@ -29,40 +60,407 @@ impl syn::parse::Parse for Output {
     }
 }
 
+impl Output {
+    /// Whether the tokens are written in the layout of protocol version 1,
+    /// which has none of the options added later.
+    fn is_v1_layout(&self) -> bool {
+        let implementor = &self.implementor;
+        implementor.attrs.is_empty()
+            && implementor.ref_kind.is_none()
+            && implementor.remote.is_none()
+            && self.inline.is_none()
+            && !self.assert_impl
+            && !self.debug
+            && self.supertraits.is_none()
+    }
+}
+
 impl template_quote::ToTokens for Output {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(quote! {(#{&self.implementor}) #{&self.target_def}});
+        tokens.extend(quote! {
+            #{crate::protocol_header(self.is_v1_layout())}
+            (#{&self.implementor}) #{&self.target_def}
+            #(if let Some(inline) = &self.inline) {
+                , inline = #inline
+            }
+            #(if self.assert_impl) {
+                , assert_impl
+            }
+            #(if self.debug) {
+                , debug
+            }
+            #(if let Some(supertraits) = &self.supertraits) {
+                , #supertraits
+            }
+        });
+    }
+}
+
+/// State of walking the supertraits for `#[implement(Trait, with_supertraits)]`,
+/// which is passed through the expansion of the target traits.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SupertraitWalk {
+    /// Paths of the traits already implemented.
+    pub done: Vec<Path>,
+    /// Traits to be implemented next.
+    pub queue: Vec<Implementor>,
+}
+
+/// Module segments of `path`, without the leading `crate`, `$crate` or `self`.
+pub fn module_of(path: &Path) -> Vec<String> {
+    let n = path.segments.len().saturating_sub(1);
+    let mut module: Vec<String> = path
+        .segments
+        .iter()
+        .take(n)
+        .map(|seg| seg.ident.to_string())
+        .collect();
+    if matches!(
+        module.first().map(String::as_str),
+        Some("crate" | "$crate" | "self")
+    ) {
+        module.remove(0);
     }
+    module
 }
 
+/// Whether `a` and `b` may refer to the same trait: the last segments are
+/// identical, and the shorter module path is a suffix of the longer one.
+fn same_trait(a: &Path, b: &Path) -> bool {
+    let (Some(last_a), Some(last_b)) = (a.segments.last(), b.segments.last()) else {
+        return false;
+    };
+    if last_a != last_b {
+        return false;
+    }
+    let (mod_a, mod_b) = (module_of(a), module_of(b));
+    let (shorter, longer) = if mod_a.len() <= mod_b.len() {
+        (mod_a, mod_b)
+    } else {
+        (mod_b, mod_a)
+    };
+    longer.ends_with(&shorter)
+}
+
+impl SupertraitWalk {
+    pub fn key(implementor: &Implementor) -> Path {
+        implementor.path.clone()
+    }
+
+    pub fn contains(&self, implementor: &Implementor) -> bool {
+        let key = Self::key(implementor);
+        self.done.iter().any(|done| same_trait(done, &key))
+            || self.queue.iter().any(|i| same_trait(&Self::key(i), &key))
+    }
+
+    /// Implement the next trait in the queue, passing the rest of the walk.
+    pub fn emit_next(mut self, target_def: &Adt, options: ImplementorOptions) -> TokenStream {
+        if self.queue.is_empty() {
+            return TokenStream::new();
+        }
+        let implementor = self.queue.remove(0);
+        implementor.emit_impl(target_def, options, Some(self))
+    }
+
+    /// Parse `with_supertraits = [(done)*] [(queue)*]` if it follows.
+    fn parse_option(input: parse::ParseStream) -> Result<Option<Self>> {
+        let fork = input.fork();
+        match fork.parse::<Ident>() {
+            Ok(ident) if ident == "with_supertraits" && fork.peek(Token![=]) => (),
+            _ => return Ok(None),
+        }
+        input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let (done_stream, queue_stream);
+        bracketed!(done_stream in input);
+        bracketed!(queue_stream in input);
+        let mut done = Vec::new();
+        while !done_stream.is_empty() {
+            let item;
+            parenthesized!(item in done_stream);
+            done.push(item.parse()?);
+        }
+        let mut queue = Vec::new();
+        while !queue_stream.is_empty() {
+            let item;
+            parenthesized!(item in queue_stream);
+            queue.push(item.parse()?);
+        }
+        Ok(Some(Self { done, queue }))
+    }
+}
+
+impl ToTokens for SupertraitWalk {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(quote! {
+            with_supertraits = [#(for path in &self.done) { (#path) }] [#(for implr in &self.queue) { (#implr) }]
+        });
+    }
+}
+
+/// Inlining hint of the generated methods, given with `inline = "..."`.
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+pub enum Inline {
+    Always,
+    Never,
+    Default,
+}
+
+impl Inline {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Inline::Always => "always",
+            Inline::Never => "never",
+            Inline::Default => "default",
+        }
+    }
+
+    /// Parse `inline = "..."` if it follows.
+    pub fn parse_option(input: parse::ParseStream) -> Result<Option<Self>> {
+        let fork = input.fork();
+        match fork.parse::<Ident>() {
+            Ok(ident) if ident == "inline" && fork.peek(Token![=]) => {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                Ok(Some(input.parse()?))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl syn::parse::Parse for Inline {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let lit = input.parse::<LitStr>()?;
+        match lit.value().as_str() {
+            "always" => Ok(Inline::Always),
+            "never" => Ok(Inline::Never),
+            "default" => Ok(Inline::Default),
+            _ => Err(Error::new_spanned(
+                &lit,
+                "expected one of \"always\", \"never\" or \"default\"",
+            )),
+        }
+    }
+}
+
+impl ToTokens for Inline {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        LitStr::new(self.as_str(), proc_macro2::Span::call_site()).to_tokens(tokens);
+    }
+}
+
+/// Specifies that the trait is implemented for `&Wrapper` or `&mut Wrapper`
+/// instead of the owned type.
 #[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub enum RefKind {
+    Ref(Token![ref]),
+    Mut(Token![mut]),
+}
+
+impl ToTokens for RefKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            RefKind::Ref(token) => token.to_tokens(tokens),
+            RefKind::Mut(token) => token.to_tokens(tokens),
+        }
+    }
+}
+
+/// Items of a foreign trait declared inline with `extern`, which is
+/// implemented without the trait declared with `#[target]`.
+#[derive(Debug, Clone)]
+pub struct RemoteTrait {
+    pub extern_token: Token![extern],
+    pub unsafety: Option<Token![unsafe]>,
+    pub items: Vec<TraitItem>,
+    /// Path to `newer_type` crate configured on `#[implement]`.
+    pub newer_type: Path,
+}
+
+impl RemoteTrait {
+    /// The trait definition used in place of the one given by `#[target]`.
+    /// The generic parameters are made from the arguments of the trait path,
+    /// using the parameters of `for<..>` by their names, so that the items can
+    /// refer to them.
+    fn trait_def(&self, implementor: &Implementor) -> ItemTrait {
+        let path = &implementor.path;
+        let last = path
+            .segments
+            .last()
+            .unwrap_or_else(|| abort!(path, "Path without segments is not supported"));
+        let implr_params: Vec<&GenericParam> = implementor
+            .generics
+            .iter()
+            .flat_map(|(_, generics)| &generics.params)
+            .collect();
+        let const_param = |path: &Path| {
+            implr_params.iter().find_map(|p| match p {
+                GenericParam::Const(p) if path.is_ident(&p.ident) => Some(p),
+                _ => None,
+            })
+        };
+        let mut generics = Generics::default();
+        if let PathArguments::AngleBracketed(args) = &last.arguments {
+            for (n, arg) in args.args.iter().enumerate() {
+                let param: GenericParam = match arg {
+                    GenericArgument::Lifetime(lt)
+                        if implr_params.iter().any(
+                            |p| matches!(p, GenericParam::Lifetime(p) if &p.lifetime == lt),
+                        ) =>
+                    {
+                        parse_quote!(#lt)
+                    }
+                    GenericArgument::Lifetime(_) => {
+                        let lt =
+                            Lifetime::new(&format!("'__newer_type_remote_{n}"), Span::call_site());
+                        parse_quote!(#lt)
+                    }
+                    GenericArgument::Type(Type::Path(TypePath { qself: None, path }))
+                        if implr_params.iter().any(
+                            |p| matches!(p, GenericParam::Type(p) if path.is_ident(&p.ident)),
+                        ) =>
+                    {
+                        parse_quote!(#path)
+                    }
+                    // `N` of `for<const N: T>`, which is parsed as a type
+                    GenericArgument::Type(Type::Path(TypePath { qself: None, path }))
+                        if const_param(path).is_some() =>
+                    {
+                        let param = const_param(path).unwrap();
+                        let (ident, ty) = (&param.ident, &param.ty);
+                        parse_quote!(const #ident: #ty)
+                    }
+                    GenericArgument::Type(_) => {
+                        let ident = Ident::new(&format!("__NewerTypeRemote{n}"), Span::call_site());
+                        parse_quote!(#ident)
+                    }
+                    GenericArgument::Const(expr) => {
+                        let ident =
+                            Ident::new(&format!("__NEWER_TYPE_REMOTE_{n}"), Span::call_site());
+                        let ty = const_arg_type(expr);
+                        parse_quote!(const #ident: #ty)
+                    }
+                    _ => continue,
+                };
+                generics.params.push(param);
+            }
+        }
+        if !generics.params.is_empty() {
+            generics.lt_token = Some(Default::default());
+            generics.gt_token = Some(Default::default());
+        }
+        let ident = &last.ident;
+        let unsafety = &self.unsafety;
+        let items = &self.items;
+        parse_quote! {
+            #unsafety trait #ident #generics {
+                #(#items)*
+            }
+        }
+    }
+}
+
+/// The type of the parameter declared for the const argument `expr` of a
+/// remote trait, taken from the literal. The other expressions are declared
+/// as `usize`, which is not checked since the argument is passed as written;
+/// the parameters of `for<const N: T>` are declared with their own types.
+fn const_arg_type(expr: &Expr) -> Type {
+    let lit = match expr {
+        Expr::Lit(ExprLit { lit, .. }) => Some(lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => Some(lit),
+            _ => None,
+        },
+        _ => None,
+    };
+    match lit {
+        Some(Lit::Bool(_)) => parse_quote!(::core::primitive::bool),
+        Some(Lit::Char(_)) => parse_quote!(::core::primitive::char),
+        Some(Lit::Byte(_)) => parse_quote!(::core::primitive::u8),
+        Some(Lit::Int(lit)) if !lit.suffix().is_empty() => {
+            let suffix = Ident::new(lit.suffix(), lit.span());
+            parse_quote!(::core::primitive::#suffix)
+        }
+        _ => parse_quote!(::core::primitive::usize),
+    }
+}
+
+/// Trait to implement. `attrs` holds `#[cfg]` attributes, which are
+/// considered in comparison, so the same trait under the different conditions
+/// is not deduplicated.
+#[derive(Debug, Clone)]
 pub struct Implementor {
+    pub attrs: Vec<Attribute>,
+    pub ref_kind: Option<RefKind>,
     pub generics: Option<(Token![for], Generics)>,
     pub path: Path,
+    pub remote: Option<RemoteTrait>,
+}
+
+impl PartialEq for Implementor {
+    fn eq(&self, other: &Self) -> bool {
+        self.attrs == other.attrs
+            && self.ref_kind == other.ref_kind
+            && self.generics == other.generics
+            && self.path == other.path
+    }
+}
+
+impl Eq for Implementor {}
+
+impl core::hash::Hash for Implementor {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.attrs.hash(state);
+        self.ref_kind.hash(state);
+        self.generics.hash(state);
+        self.path.hash(state);
+    }
 }
 
 impl core::fmt::Display for Implementor {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        quote! {#{&self.path}}.fmt(f)
+        quote! {#{&self.ref_kind} #{&self.path}}.fmt(f)
     }
 }
 
 impl ToTokens for Implementor {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.extend(quote! {
+                #(for attr in &self.attrs) { #attr }
+                #{&self.ref_kind}
                 #(if let Some((for_token, generics)) = &self.generics) {
                     #for_token #generics
                 }
+                #(if let Some(remote) = &self.remote) {
+                    #{&remote.extern_token} #{&remote.unsafety}
+                }
                 #{&self.path}
                 #(if let Some((_, generics)) = &self.generics) {
                     #{&generics.where_clause}
                 }
+                #(if let Some(remote) = &self.remote) {
+                    { #(for item in &remote.items) { #item } }
+                }
         });
     }
 }
 
 impl syn::parse::Parse for Implementor {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        if let Some(attr) = attrs.iter().find(|attr| !attr.path().is_ident("cfg")) {
+            return Err(Error::new_spanned(attr, "only `#[cfg]` is supported here"));
+        }
+        let ref_kind = if let Some(token) = input.parse::<Option<Token![ref]>>()? {
+            Some(RefKind::Ref(token))
+        } else {
+            input.parse::<Option<Token![mut]>>()?.map(RefKind::Mut)
+        };
         let mut generics = if let Ok(for_token) = input.parse::<Token![for]>() {
             let lt_token = Some(input.parse::<Token![<]>()?);
             let mut params = Punctuated::new();
@@ -86,6 +484,12 @@ impl syn::parse::Parse for Implementor {
         } else {
             None
         };
+        let extern_token = input.parse::<Option<Token![extern]>>()?;
+        let unsafety = if extern_token.is_some() {
+            input.parse::<Option<Token![unsafe]>>()?
+        } else {
+            None
+        };
         let path: Path = input.parse()?;
         if let Some(seg) = path.segments.last() {
             if let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) =
@@ -102,8 +506,30 @@ impl syn::parse::Parse for Implementor {
         if let Some(generics) = &mut generics {
             generics.1.where_clause = input.parse::<Option<WhereClause>>()?;
         }
-        if generics.is_none() || input.is_empty() {
-            Ok(Implementor { generics, path })
+        let remote = if let Some(extern_token) = extern_token {
+            let content;
+            braced!(content in input);
+            let mut items = Vec::new();
+            while !content.is_empty() {
+                items.push(content.parse()?);
+            }
+            Some(RemoteTrait {
+                extern_token,
+                unsafety,
+                items,
+                newer_type: parse_quote!(::newer_type),
+            })
+        } else {
+            None
+        };
+        if generics.is_none() || remote.is_some() || input.is_empty() {
+            Ok(Implementor {
+                attrs,
+                ref_kind,
+                generics,
+                path,
+                remote,
+            })
         } else {
             Err(input.error("Bad trailing tokens"))
         }
@@ -111,34 +537,270 @@ impl syn::parse::Parse for Implementor {
 }
 
 impl Implementor {
-    fn emit_impl(&self, target_def: &Adt) -> TokenStream {
+    pub fn emit_impl(
+        &self,
+        target_def: &Adt,
+        options: ImplementorOptions,
+        supertraits: Option<SupertraitWalk>,
+    ) -> TokenStream {
         let input = Output {
-            implementor: self.clone(),
+            implementor: Implementor {
+                attrs: Vec::new(),
+                ..self.clone()
+            },
             target_def: target_def.clone(),
+            inline: options.inline,
+            assert_impl: options.assert_impl,
+            debug: options.debug,
+            supertraits,
         };
+        if let Some(remote) = &self.remote {
+            // Implemented right here, since the trait has no macro to forward
+            // to. The items are checked against the real trait by rustc.
+            let imp = implement_internal::Input {
+                implementor: input.implementor,
+                adt: input.target_def,
+                trait_def: remote.trait_def(self),
+                alternative: None,
+                newer_type: remote.newer_type.clone(),
+                // Not referred, since no types are carried by the repeater.
+                referrer: parse_quote!(()),
+                repeater: parse_quote!(crate::__NewerTypeRepeater),
+                nonce: crate::nonce("extern", &self.path),
+                inline: input.inline,
+                assert_impl: input.assert_impl,
+                debug: input.debug,
+                target_inline: None,
+                supertraits: input.supertraits,
+            }
+            .implement_internal();
+            return quote! {
+                #(for attr in &self.attrs) { #attr }
+                const _: () = { #imp };
+            };
+        }
         let mut path = self.path.clone();
         if let Some(seg) = path.segments.last_mut() {
             seg.arguments = PathArguments::None
         }
         quote! {
+            #(for attr in &self.attrs) { #attr }
             #path! { #input }
         }
     }
 }
 
-#[derive(Parse, PartialEq, Eq, Debug, Hash, Clone)]
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+pub enum OnInvalid {
+    Panic,
+    DebugAssert,
+    Rollback,
+}
+
+impl OnInvalid {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OnInvalid::Panic => "panic",
+            OnInvalid::DebugAssert => "debug_assert",
+            OnInvalid::Rollback => "rollback",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub struct Validation {
+    pub check: Path,
+    pub on_invalid: OnInvalid,
+}
+
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
 pub struct Argument {
-    #[call(Punctuated::parse_terminated)]
     pub implementors: Punctuated<Implementor, Token![,]>,
+    pub validation: Option<Validation>,
+    pub newtype: Option<Ident>,
+    pub transparent: Option<Ident>,
+    pub inline: Option<Inline>,
+    pub with_supertraits: Option<Ident>,
+    pub assert_impl: Option<Ident>,
+    pub debug: Option<Ident>,
+    pub newer_type: Option<Path>,
+}
+
+/// Options applied to the implementors in the same `#[implement(...)]`
+/// attribute.
+#[derive(Clone, Copy, Default)]
+pub struct ImplementorOptions {
+    pub inline: Option<Inline>,
+    pub with_supertraits: bool,
+    pub assert_impl: bool,
+    pub debug: bool,
+}
+
+/// Parse a standalone identifier `name`, which is followed by `,` or the end of
+/// input.
+pub fn parse_flag(input: parse::ParseStream, name: &str) -> Option<Ident> {
+    let fork = input.fork();
+    match fork.parse::<Ident>() {
+        Ok(ident) if ident == name && (fork.is_empty() || fork.peek(Token![,])) => {
+            input.parse::<Ident>().ok()
+        }
+        _ => None,
+    }
+}
+
+impl syn::parse::Parse for Argument {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let mut implementors = Punctuated::new();
+        let mut check = None;
+        let mut on_invalid = None;
+        let mut newtype = None;
+        let mut transparent = None;
+        let mut inline = None;
+        let mut with_supertraits = None;
+        let mut assert_impl = None;
+        let mut debug = None;
+        let mut newer_type = None;
+        while !input.is_empty() {
+            if let Some(ident) = parse_flag(input, "newtype") {
+                newtype = Some(ident);
+            } else if let Some(ident) = parse_flag(input, "with_supertraits") {
+                with_supertraits = Some(ident);
+            } else if let Some(ident) = parse_flag(input, "assert_impl") {
+                assert_impl = Some(ident);
+            } else if let Some(ident) = parse_flag(input, "debug") {
+                debug = Some(ident);
+            } else if let Some(ident) = parse_flag(input, "transparent") {
+                transparent = Some(ident);
+            } else if input.peek(Ident) && input.peek2(Token![=]) {
+                let ident = input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                match ident.to_string().as_str() {
+                    "validate" => {
+                        check = Some(input.parse::<Path>()?);
+                    }
+                    "inline" => {
+                        inline = Some(input.parse()?);
+                    }
+                    "newer_type" => {
+                        newer_type = Some(input.parse()?);
+                    }
+                    "on_invalid" => {
+                        let lit = input.parse::<LitStr>()?;
+                        on_invalid =
+                            Some(match lit.value().as_str() {
+                                "panic" => OnInvalid::Panic,
+                                "debug_assert" => OnInvalid::DebugAssert,
+                                "rollback" => OnInvalid::Rollback,
+                                _ => return Err(Error::new_spanned(
+                                    &lit,
+                                    "expected one of \"panic\", \"debug_assert\" or \"rollback\"",
+                                )),
+                            });
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(&ident, "Unsupported argument"));
+                    }
+                }
+            } else {
+                implementors.push_value(input.parse()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            let comma = input.parse::<Token![,]>()?;
+            if !implementors.empty_or_trailing() {
+                implementors.push_punct(comma);
+            }
+        }
+        let validation = match (check, on_invalid) {
+            (Some(check), on_invalid) => Some(Validation {
+                check,
+                on_invalid: on_invalid.unwrap_or(OnInvalid::Panic),
+            }),
+            (None, Some(_)) => {
+                return Err(input.error("`on_invalid` requires `validate` argument"));
+            }
+            (None, None) => None,
+        };
+        Ok(Argument {
+            implementors,
+            validation,
+            newtype,
+            transparent,
+            inline,
+            with_supertraits,
+            assert_impl,
+            debug,
+            newer_type,
+        })
+    }
 }
 
 impl ToTokens for Argument {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.implementors.to_tokens(tokens);
+        if !self.implementors.empty_or_trailing() {
+            <Token![,]>::default().to_tokens(tokens);
+        }
+        tokens.extend(quote! {
+            #(if let Some(validation) = &self.validation) {
+                validate = #{&validation.check},
+                on_invalid = #{validation.on_invalid.as_str()},
+            }
+            #(if let Some(newtype) = &self.newtype) {
+                #newtype,
+            }
+            #(if let Some(transparent) = &self.transparent) {
+                #transparent,
+            }
+            #(if let Some(inline) = &self.inline) {
+                inline = #inline,
+            }
+            #(if let Some(with_supertraits) = &self.with_supertraits) {
+                #with_supertraits,
+            }
+            #(if let Some(assert_impl) = &self.assert_impl) {
+                #assert_impl,
+            }
+            #(if let Some(debug) = &self.debug) {
+                #debug,
+            }
+            #(if let Some(newer_type) = &self.newer_type) {
+                newer_type = #newer_type,
+            }
+        });
     }
 }
 
 impl Argument {
+    /// Path to `newer_type` crate given with `newer_type = ...`.
+    pub fn newer_type(&self) -> Path {
+        self.newer_type
+            .clone()
+            .unwrap_or_else(|| parse_quote!(::newer_type))
+    }
+
+    fn implementor_options(&self) -> ImplementorOptions {
+        ImplementorOptions {
+            inline: self.inline,
+            with_supertraits: self.with_supertraits.is_some(),
+            assert_impl: self.assert_impl.is_some(),
+            debug: self.debug.is_some(),
+        }
+    }
+
+    /// Merge arguments given in another `#[implement(...)]` attribute.
+    fn merge(&mut self, other: Self) {
+        if let (Some(prev), Some(v)) = (&self.validation, &other.validation) {
+            abort!(&v.check, "`validate` is specified more than once"; note = prev.check.span() => "first specified here");
+        }
+        self.implementors.extend(other.implementors);
+        self.validation = self.validation.take().or(other.validation);
+        self.newtype = self.newtype.take().or(other.newtype);
+        self.transparent = self.transparent.take().or(other.transparent);
+        self.newer_type = self.newer_type.take().or(other.newer_type);
+    }
+
     pub fn from_attr(attr: &Attribute) -> Result<Option<Self>> {
         match &attr.meta {
             Meta::List(MetaList { path, tokens, .. }) if path.is_ident("implement") => {
@@ -175,6 +837,34 @@ impl template_quote::ToTokens for Adt {
 }
 
 impl Adt {
+    pub fn attrs(&self) -> &Vec<Attribute> {
+        match self {
+            Adt::Enum(item_enum) => &item_enum.attrs,
+            Adt::Struct(item_struct) => &item_struct.attrs,
+        }
+    }
+
+    pub fn attrs_mut(&mut self) -> &mut Vec<Attribute> {
+        match self {
+            Adt::Enum(item_enum) => &mut item_enum.attrs,
+            Adt::Struct(item_struct) => &mut item_struct.attrs,
+        }
+    }
+
+    pub fn ident(&self) -> &Ident {
+        match self {
+            Adt::Enum(item_enum) => &item_enum.ident,
+            Adt::Struct(item_struct) => &item_struct.ident,
+        }
+    }
+
+    pub fn generics(&self) -> &Generics {
+        match self {
+            Adt::Enum(item_enum) => &item_enum.generics,
+            Adt::Struct(item_struct) => &item_struct.generics,
+        }
+    }
+
     pub fn generics_mut(&mut self) -> &mut Generics {
         match self {
             Adt::Enum(item_enum) => &mut item_enum.generics,
@@ -182,7 +872,31 @@ impl Adt {
         }
     }
 
-    fn collect_implementors(&mut self) -> Vec<Implementor> {
+    /// Get the validation specified with `#[implement(validate = ...)]` on the
+    /// item.
+    pub fn validation(&self) -> Option<Validation> {
+        self.attrs()
+            .iter()
+            .filter_map(|attr| Argument::from_attr(attr).unwrap_or_abort())
+            .find_map(|arg| arg.validation)
+    }
+
+    /// Remove `#[implement(...)]` attributes on the item itself and returns
+    /// their arguments.
+    fn take_item_arguments(&mut self) -> Vec<Argument> {
+        let mut ret = Vec::new();
+        self.attrs_mut().retain(|attr| {
+            if let Some(arg) = Argument::from_attr(attr).unwrap_or_abort() {
+                ret.push(arg);
+                false
+            } else {
+                true
+            }
+        });
+        ret
+    }
+
+    fn collect_implementors(&mut self) -> Vec<(Implementor, ImplementorOptions)> {
         let mut ret = Vec::new();
         let mut proceed_field = |field: &mut Field| {
             field.attrs = field
@@ -191,9 +905,16 @@ impl Adt {
                 .into_iter()
                 .filter(|attr| {
                     if let Some(arg) = Argument::from_attr(attr).unwrap_or_abort() {
+                        if let Some(validation) = &arg.validation {
+                            abort!(&validation.check, "`validate` is not allowed on fields"; hint = "put it on the struct instead");
+                        }
+                        if let Some(flag) = arg.newtype.iter().chain(&arg.transparent).next() {
+                            abort!(flag, "`{}` is not allowed on fields", flag; hint = "put it on the struct instead");
+                        }
+                        let options = arg.implementor_options();
                         for implem in arg.implementors {
-                            if !ret.iter().any(|a| a == &implem) {
-                                ret.push(implem);
+                            if !ret.iter().any(|(a, _)| a == &implem) {
+                                ret.push((implem, options));
                             }
                         }
                         false
@@ -222,15 +943,125 @@ impl Adt {
 }
 
 pub fn implement(arg: &Argument, target_def: &Adt) -> TokenStream {
+    let (item, impls) = expand(arg, target_def);
+    quote! {
+        #item
+        #impls
+    }
+}
+
+/// Entry point of `#[derive(Implement)]`, which reads `#[implement(...)]` on
+/// the item and the fields as helper attributes, and emits only the
+/// implementations.
+pub fn derive(target_def: &Adt) -> TokenStream {
+    let arg: Argument = parse_quote!();
+    let (_, impls) = expand(&arg, target_def);
+    let has_repr_transparent = target_def.attrs().iter().any(|attr| {
+        attr.path().is_ident("repr")
+            && attr
+                .parse_args::<Ident>()
+                .map_or(false, |repr| repr == "transparent")
+    });
+    if let Some(transparent) = target_def
+        .attrs()
+        .iter()
+        .filter_map(|attr| Argument::from_attr(attr).unwrap_or_abort())
+        .find_map(|arg| arg.transparent)
+        .filter(|_| !has_repr_transparent)
+    {
+        abort!(
+            transparent,
+            "`#[derive(Implement)]` cannot add `#[repr(transparent)]` to the struct";
+            hint = "add `#[repr(transparent)]` to the struct"
+        );
+    }
+    impls
+}
+
+/// Returns the item to be emitted, with the `#[implement(...)]` attributes
+/// removed, and the implementations.
+fn expand(arg: &Argument, target_def: &Adt) -> (Adt, TokenStream) {
     let mut copied_target_def = target_def.clone();
-    let imp: TokenStream = copied_target_def
-        .collect_implementors()
-        .into_iter()
-        .chain(arg.implementors.iter().cloned())
-        .map(|implr| implr.emit_impl(target_def))
+    let mut forwarded_def = target_def.clone();
+    forwarded_def.take_item_arguments();
+    let item_args = copied_target_def.take_item_arguments();
+    let mut implementors = copied_target_def.collect_implementors();
+    let mut arg = arg.clone();
+    for a in std::iter::once(&arg).chain(&item_args) {
+        let options = a.implementor_options();
+        implementors.extend(a.implementors.iter().map(|implr| (implr.clone(), options)));
+    }
+    for other in item_args {
+        arg.merge(other);
+    }
+    for (implr, options) in &mut implementors {
+        if let Some(remote) = &mut implr.remote {
+            remote.newer_type = arg.newer_type();
+            // Remote traits have no supertraits to walk, and are implemented
+            // right away without passing the walk through the macros.
+            options.with_supertraits = false;
+        }
+    }
+    let mut marker_checks = TokenStream::new();
+    for (implr, _) in implementors
+        .iter()
+        .filter(|(implr, _)| implr.remote.is_none())
+    {
+        diagnostics::check_target_path(&implr.path);
+        if implr.generics.is_none() {
+            marker_checks.extend(diagnostics::check_target_marker(&implr.path));
+        }
+    }
+    if let Some(validation) = &arg.validation {
+        let forwarded_arg = Argument {
+            implementors: Punctuated::new(),
+            validation: Some(validation.clone()),
+            newtype: None,
+            transparent: None,
+            inline: None,
+            with_supertraits: None,
+            assert_impl: None,
+            debug: None,
+            newer_type: None,
+        };
+        forwarded_def
+            .attrs_mut()
+            .push(parse_quote!(#[implement(#forwarded_arg)]));
+    }
+    if let Some(transparent) = &arg.transparent {
+        newtype::add_repr_transparent(&mut copied_target_def, transparent);
+    }
+    let imp: TokenStream = implementors
+        .iter()
+        .filter(|(_, options)| !options.with_supertraits)
+        .map(|(implr, options)| implr.emit_impl(&forwarded_def, *options, None))
         .collect();
-    quote! {
-        #copied_target_def
-        #imp
+    // The traits with `with_supertraits` are implemented one by one, walking the
+    // supertraits and skipping the traits already implemented.
+    let mut walk = SupertraitWalk {
+        done: implementors
+            .iter()
+            .filter(|(_, options)| !options.with_supertraits)
+            .map(|(implr, _)| SupertraitWalk::key(implr))
+            .collect(),
+        queue: Vec::new(),
+    };
+    let mut walk_options = ImplementorOptions::default();
+    for (implr, options) in &implementors {
+        if options.with_supertraits && !walk.contains(implr) {
+            walk_options.inline = walk_options.inline.or(options.inline);
+            walk_options.assert_impl |= options.assert_impl;
+            walk_options.debug |= options.debug;
+            walk.queue.push(implr.clone());
+        }
     }
+    let walk_imp = walk.emit_next(&forwarded_def, walk_options);
+    let impls = quote! {
+        #imp
+        #walk_imp
+        #marker_checks
+        #{newtype::emit_inherent_items(target_def, &arg)}
+        #{newtype::emit_newtype_impl(target_def, &arg)}
+    };
+    (copied_target_def, impls)
 }
@@ -1,7 +1,9 @@
+use crate::debug;
 use crate::implement::{
-    Adt, Argument as ImplementArgument, Implementor, Output as ImplementOutput,
+    module_of, Adt, Argument as ImplementArgument, Implementor, ImplementorOptions, Inline,
+    OnInvalid, Output as ImplementOutput, RefKind, SupertraitWalk, Validation,
 };
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use proc_macro_error::abort;
 use std::collections::{HashMap, HashSet};
 use syn::punctuated::Punctuated;
@@ -9,7 +11,7 @@ use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
 use syn::*;
-use template_quote::quote;
+use template_quote::{quote, ToTokens};
 use type_leak::Referrer;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -22,6 +24,11 @@ pub struct Input {
     pub referrer: Referrer,
     pub repeater: Path,
     pub nonce: u64,
+    pub inline: Option<Inline>,
+    pub assert_impl: bool,
+    pub debug: bool,
+    pub target_inline: Option<Inline>,
+    pub supertraits: Option<SupertraitWalk>,
 }
 
 impl syn::parse::Parse for Input {
@@ -31,7 +38,12 @@ impl syn::parse::Parse for Input {
         let ImplementOutput {
             implementor,
             target_def,
+            inline,
+            assert_impl,
+            debug,
+            supertraits,
         } = impl_output.parse()?;
+        crate::parse_protocol_version(input, "`#[target]` trait")?;
         let trait_def = input.parse()?;
         input.parse::<Token![,]>()?;
         let alternative = if !input.peek(Token![,]) {
@@ -48,6 +60,8 @@ impl syn::parse::Parse for Input {
         input.parse::<Token![,]>()?;
         let nonce: LitInt = input.parse()?;
         let _ = input.parse::<Token![,]>();
+        let target_inline = Inline::parse_option(input)?;
+        let _ = input.parse::<Token![,]>();
         if input.is_empty() {
             Ok(Self {
                 implementor,
@@ -58,6 +72,11 @@ impl syn::parse::Parse for Input {
                 referrer,
                 repeater,
                 nonce: nonce.base10_parse()?,
+                inline,
+                assert_impl,
+                debug,
+                target_inline,
+                supertraits,
             })
         } else {
             Err(input.error("Bad trailing tokens"))
@@ -70,8 +89,15 @@ impl template_quote::ToTokens for Input {
         let impl_output = ImplementOutput {
             implementor: self.implementor.clone(),
             target_def: self.adt.clone(),
+            inline: self.inline,
+            assert_impl: self.assert_impl,
+            debug: self.debug,
+            supertraits: self.supertraits.clone(),
         };
-        tokens.extend(quote! {(#impl_output)});
+        tokens.extend(quote! {
+            (#impl_output)
+            #{crate::protocol_header(self.target_inline.is_none())}
+        });
         self.trait_def.to_tokens(tokens);
         <Token![,]>::default().to_tokens(tokens);
         self.alternative.to_tokens(tokens);
@@ -80,7 +106,12 @@ impl template_quote::ToTokens for Input {
         <Token![,]>::default().to_tokens(tokens);
         self.repeater.to_tokens(tokens);
         <Token![,]>::default().to_tokens(tokens);
-        tokens.extend(quote! {#{self.nonce}})
+        tokens.extend(quote! {
+            #{self.nonce}
+            #(if let Some(inline) = &self.target_inline) {
+                , inline = #inline
+            }
+        })
     }
 }
 
@@ -117,6 +148,37 @@ where
         )
 }
 
+/// Adds a `for<'a>` binder to the bounds on the associated types of `Self` in
+/// the where clause of the methods, like `Self::Item: Ord` of
+/// `Iterator::max`. Without the binder, they are rejected as trivially false
+/// bounds when the associated type does not satisfy them
+/// (rust-lang/rust#48214).
+fn bind_self_assoc_bounds(where_clause: &mut WhereClause) {
+    let lifetime = Lifetime::new("'__newer_type_bound", Span::call_site());
+    for pred in where_clause.predicates.iter_mut() {
+        if let WherePredicate::Type(PredicateType {
+            lifetimes: lifetimes @ None,
+            bounded_ty,
+            ..
+        }) = pred
+        {
+            struct MentionsSelf(bool);
+            impl visit::Visit<'_> for MentionsSelf {
+                fn visit_path(&mut self, i: &Path) {
+                    self.0 |= i.leading_colon.is_none()
+                        && i.segments.first().map_or(false, |seg| seg.ident == "Self");
+                    visit::visit_path(self, i)
+                }
+            }
+            let mut mentions_self = MentionsSelf(false);
+            visit::Visit::visit_type(&mut mentions_self, bounded_ty);
+            if mentions_self.0 && check_is_self_ty(bounded_ty).is_none() {
+                *lifetimes = Some(parse_quote!(for<#lifetime>));
+            }
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 struct CorrectAssocTys(HashSet<Ident>);
 
@@ -168,10 +230,14 @@ impl ModifyGenerics {
 
     fn append_const(&mut self, ident: &mut Ident, nonce: u64) {
         let nident = Ident::new(
-            &format!("NewerTypeTypeParam{}Of{}", &ident, nonce),
+            &format!(
+                "NEWER_TYPE_CONST_PARAM_{}_OF_{}",
+                ident.to_string().to_uppercase(),
+                nonce
+            ),
             ident.span(),
         );
-        self.const_map.insert(ident.clone(), parse_quote!(nident));
+        self.const_map.insert(ident.clone(), parse_quote!(#nident));
         *ident = nident;
     }
 
@@ -205,6 +271,9 @@ impl VisitMut for ModifyGenerics {
                     visit_mut::visit_path_mut(self, &mut implr.path);
                 }
             }
+            if let Some(validation) = &mut implr_arg.validation {
+                visit_mut::visit_path_mut(self, &mut validation.check);
+            }
             *i = parse_quote!(#[implement(#implr_arg)]);
         }
     }
@@ -232,6 +301,22 @@ impl VisitMut for ModifyGenerics {
         visit_mut::visit_type_mut(self, i);
     }
 
+    fn visit_generic_argument_mut(&mut self, i: &mut GenericArgument) {
+        // A const argument of a single identifier is parsed as a type
+        if let GenericArgument::Type(Type::Path(TypePath { qself: None, path })) = i {
+            if let Some(nconst) = path.get_ident().and_then(|ident| self.const_map.get(ident)) {
+                *i = match nconst {
+                    Expr::Lit(_) | Expr::Path(_) | Expr::Block(_) => {
+                        GenericArgument::Const(nconst.clone())
+                    }
+                    _ => GenericArgument::Const(parse_quote!({ #nconst })),
+                };
+                return;
+            }
+        }
+        visit_mut::visit_generic_argument_mut(self, i);
+    }
+
     fn visit_expr_mut(&mut self, i: &mut Expr) {
         match i {
             Expr::Path(ExprPath { qself, path, .. }) if qself.is_none() => {
@@ -282,6 +367,11 @@ fn check_has_self_ty(ty: &Type) -> Option<Path> {
                 Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => {
                     self.0 = Some(path.clone());
                 }
+                // `<Self as Trait>::Assoc` is a projection, not `Self` itself
+                Type::Path(TypePath {
+                    qself: Some(qself),
+                    path,
+                }) if check_is_self_ty(&qself.ty).is_some() => self.visit_path(path),
                 _ => syn::visit::visit_type(self, i),
             }
         }
@@ -306,6 +396,208 @@ fn check_is_self_ty(ty: &Type) -> Option<TokenStream> {
     }
 }
 
+fn check_has_borrow(ty: &Type) -> Option<Span> {
+    struct CheckHasBorrow(Option<Span>);
+    impl Visit<'_> for CheckHasBorrow {
+        fn visit_type_reference(&mut self, i: &TypeReference) {
+            self.0 = Some(i.span());
+        }
+
+        fn visit_lifetime(&mut self, i: &Lifetime) {
+            self.0 = Some(i.span());
+        }
+    }
+    let mut checker = CheckHasBorrow(None);
+    checker.visit_type(ty);
+    checker.0
+}
+
+fn check_has_impl_trait(ty: &Type) -> Option<Span> {
+    struct CheckHasImplTrait(Option<Span>);
+    impl Visit<'_> for CheckHasImplTrait {
+        fn visit_type_impl_trait(&mut self, i: &TypeImplTrait) {
+            self.0 = Some(i.span());
+        }
+    }
+    let mut checker = CheckHasImplTrait(None);
+    checker.visit_type(ty);
+    checker.0
+}
+
+/// Traits implemented by `newer_type::__private::Either`, in addition to the
+/// auto traits.
+const EITHER_TRAITS: &[&str] = &[
+    "Iterator",
+    "DoubleEndedIterator",
+    "ExactSizeIterator",
+    "FusedIterator",
+    "Future",
+    "Send",
+    "Sync",
+    "Unpin",
+    "Sized",
+];
+
+/// Check that `newer_type::__private::Either` can be returned as the
+/// `impl Trait` from the methods implemented for enums.
+fn check_either_bounds(impl_trait: &TypeImplTrait) {
+    for bound in &impl_trait.bounds {
+        let TypeParamBound::Trait(TraitBound { path, .. }) = bound else {
+            continue;
+        };
+        let Some(seg) = path.segments.last() else {
+            continue;
+        };
+        if !EITHER_TRAITS.iter().any(|name| seg.ident == name) {
+            abort!(
+                path,
+                "`impl {}` cannot be returned from the methods implemented for enums",
+                &seg.ident;
+                note = "the values returned from the variants are wrapped into a sum type, which implements only `Iterator`, `DoubleEndedIterator`, `ExactSizeIterator`, `FusedIterator` and `Future`";
+                hint = "return a boxed trait object like `Box<dyn Trait>` instead"
+            );
+        }
+    }
+}
+
+/// Wrap the value returned from `arm`-th match arm into nested
+/// `newer_type::__private::Either`.
+fn wrap_either(newer_type: &Path, value: TokenStream, arm: usize, n_arms: usize) -> TokenStream {
+    let mut ret = if arm + 1 < n_arms {
+        quote!(#newer_type::__private::Either::Left(#value))
+    } else {
+        value
+    };
+    for _ in 0..arm {
+        ret = quote!(#newer_type::__private::Either::Right(#ret));
+    }
+    ret
+}
+
+fn is_mut_ref_param(arg: &FnArg) -> bool {
+    match arg {
+        FnArg::Receiver(Receiver {
+            reference: Some(_),
+            mutability: Some(_),
+            ..
+        }) => true,
+        FnArg::Typed(PatType { ty, .. }) => matches!(
+            ty.as_ref(),
+            Type::Reference(TypeReference { mutability: Some(_), elem, .. })
+                if check_is_self_ty(elem.as_ref()).is_some()
+        ),
+        _ => false,
+    }
+}
+
+/// Wrap the body of a delegated method to check the invariant of validated
+/// newtype after the inner value is mutated.
+fn emit_validated_body(
+    adt_ident: &Ident,
+    (validation, member): (&Validation, &Member),
+    sig: &Signature,
+    preds: &[(usize, Ident, TokenStream)],
+    body: TokenStream,
+) -> TokenStream {
+    let mut_preds = preds
+        .iter()
+        .filter(|(i, _, _)| is_mut_ref_param(&sig.inputs[*i]))
+        .map(|(_, ident, _)| ident)
+        .collect::<Vec<_>>();
+    if mut_preds.is_empty() {
+        return body;
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        abort!(
+            asyncness,
+            "cannot keep the invariant of validated `{}` in async method",
+            adt_ident
+        );
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        if let Some(span) = check_has_borrow(ty.as_ref()) {
+            abort!(
+                span,
+                "cannot keep the invariant of validated `{}` in `{}`", adt_ident, &sig.ident;
+                note = "the returned value may borrow the inner value mutably"
+            );
+        }
+    }
+    let check = &validation.check;
+    let ret = Ident::new("__newer_type_ret", Span::call_site());
+    let message = format!(
+        "invariant of `{}` is violated in `{}`",
+        adt_ident, &sig.ident
+    );
+    match validation.on_invalid {
+        OnInvalid::Panic => quote! {
+            let #ret = { #body };
+            #(for pred in &mut_preds) {
+                if !#check(&#pred.#member) {
+                    ::core::panic!(#message);
+                }
+            }
+            #ret
+        },
+        OnInvalid::DebugAssert => quote! {
+            let #ret = { #body };
+            #(for pred in &mut_preds) {
+                ::core::debug_assert!(#check(&#pred.#member), #message);
+            }
+            #ret
+        },
+        OnInvalid::Rollback => {
+            let backups = (0..mut_preds.len())
+                .map(|i| Ident::new(&format!("__newer_type_backup_{i}"), Span::call_site()))
+                .collect::<Vec<_>>();
+            quote! {
+                #(for (pred, backup) in mut_preds.iter().zip(&backups)) {
+                    let #backup = ::core::clone::Clone::clone(&#pred.#member);
+                }
+                let #ret = { #body };
+                #(for (pred, backup) in mut_preds.iter().zip(&backups)) {
+                    if !#check(&#pred.#member) {
+                        #pred.#member = #backup;
+                    }
+                }
+                #ret
+            }
+        }
+    }
+}
+
+/// Returns `true` if the method has `where Self: Sized` bound.
+fn has_self_sized_bound(sig: &Signature) -> bool {
+    sig.generics.where_clause.iter().any(|wc| {
+        wc.predicates.iter().any(|pred| match pred {
+            WherePredicate::Type(PredicateType {
+                bounded_ty: Type::Path(TypePath { qself: None, path }),
+                bounds,
+                ..
+            }) if path.is_ident("Self") => bounds.iter().any(|bound| {
+                matches!(bound, TypeParamBound::Trait(TraitBound { path, .. })
+                    if path.segments.last().map_or(false, |seg| seg.ident == "Sized"))
+            }),
+            _ => false,
+        })
+    })
+}
+
+/// Abort if the method takes `Self` by value, which is impossible for
+/// wrappers of dynamically sized types.
+fn check_unsized_receiver(sig: &Signature, inner_ty: &Type) {
+    for (_, ident, ref_tokens) in find_pred_param(&sig.inputs) {
+        if ref_tokens.is_empty() {
+            abort!(
+                ident,
+                "`{}` takes `Self` by value, which is not supported for dynamically sized inner type", &sig.ident;
+                note = inner_ty.span() => "`{}` is not `Sized`", inner_ty.to_token_stream();
+                hint = "add `where Self: Sized` to the method in the trait definition"
+            );
+        }
+    }
+}
+
 fn find_pred_param<'a>(
     args: impl IntoIterator<Item = &'a FnArg>,
 ) -> Vec<(usize, Ident, TokenStream)> {
@@ -410,12 +702,13 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
     fn emit_trait_fn(
         &self,
         trait_: &Path,
-        implementor: &Implementor,
-        mut input: TraitItemFn,
+        input: &Input,
+        mut trait_fn: TraitItemFn,
         nonce: u64,
         leaked_ty_visitor: &mut impl VisitMut,
     ) -> TokenStream {
-        for param in input.sig.inputs.iter_mut() {
+        let implementor = &input.implementor;
+        for param in trait_fn.sig.inputs.iter_mut() {
             if let FnArg::Typed(PatType { pat, .. }) = param {
                 let mut cnt = 0usize;
                 update_pat_names(pat.as_mut(), &mut |span| {
@@ -424,20 +717,53 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
                 })
             }
         }
-        let (impl_generics, _, where_clause) = input.sig.generics.split_for_impl();
-        let preds = find_pred_param(&input.sig.inputs);
+        let (impl_generics, _, where_clause) = trait_fn.sig.generics.split_for_impl();
+        let preds = find_pred_param(&trait_fn.sig.inputs);
+        if let Some(ref_kind) = &implementor.ref_kind {
+            if let Some((_, ident, ref_tokens)) = preds.iter().find(|(_, _, r)| !r.is_empty()) {
+                abort!(
+                    ident,
+                    "`{}` takes `{}`, which is not supported for `{}` implementor",
+                    &trait_fn.sig.ident,
+                    quote!(#ref_tokens Self).to_string().replace("& ", "&"),
+                    ref_kind.to_token_stream();
+                    note = "only methods taking `Self` by value can be forwarded to references";
+                    hint = "remove `{}` to implement the trait for the owned type", ref_kind.to_token_stream()
+                );
+            }
+        }
         if preds.is_empty() {
-            abort!(&input.sig.inputs, "no `Self` type is found"; hint = "exact one `self` type is required in parameters");
+            abort!(&trait_fn.sig.inputs, "no `Self` type is found"; hint = "exact one `self` type is required in parameters");
         } else if preds.len() > 1 && self.get_predicate_types(implementor).is_empty() {
             // self is enum
             abort!(&preds[1].1, "multiple `Self` type is not supported"; hint = preds[0].1.span() => "first `Self` type is here");
         }
-        if let ReturnType::Type(_, ty) = &input.sig.output {
+        // The number of the match arms, which is greater than 1 for enums
+        let n_arms = self.get_predicate_types(implementor).len();
+        let mut is_rpit = false;
+        if let ReturnType::Type(_, ty) = &trait_fn.sig.output {
             if let Some(reason) = check_has_self_ty(ty.as_ref()) {
                 abort!(reason, "`Self` type is not allowed in return position");
             }
+            is_rpit = matches!(ty.as_ref(), Type::ImplTrait(_));
+            if let (Type::ImplTrait(impl_trait), true) = (ty.as_ref(), n_arms > 1) {
+                check_either_bounds(impl_trait);
+            }
+            if let (false, true, Some(span)) = (is_rpit, n_arms > 1, check_has_impl_trait(ty)) {
+                abort!(
+                    span,
+                    "nested `impl Trait` in return position is not supported for enums";
+                    hint = "return `impl Trait` directly"
+                );
+            }
         }
-        let mut sig = input.sig.clone();
+        let pred_spans = self
+            .get_predicate_types(implementor)
+            .iter()
+            .map(|ty| ty.span())
+            .collect::<Vec<_>>();
+        let mut sig = trait_fn.sig.clone();
+        let mut arm = 0usize;
         leaked_ty_visitor.visit_signature_mut(&mut sig);
         let body = self.emit_body(&preds, implementor, |pred_params| {
             let process_pat = |mut pat: Pat| -> Pat{
@@ -450,8 +776,8 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
                 PatVisitor.visit_pat_mut(&mut pat);
                 pat
             };
-            quote! {
-                <_ as #trait_> :: #{&sig.ident} (
+            let call = quote! {
+                #{locate_at(quote!(<_ as #trait_> :: #{&sig.ident}), pred_spans[arm])} (
                     #(for (i, param) in sig.inputs.iter().enumerate()), {
                         #(if let Some((_, pred_param)) = preds.iter().zip(pred_params).find(|((n, _, _), _)| &i == n)) {
                             #pred_param
@@ -466,9 +792,54 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
                         }
                     }
                 )
+                #(if sig.asyncness.is_some()) { .await }
+            };
+            arm += 1;
+            if is_rpit && n_arms > 1 {
+                // Each arm returns different opaque type, so wrap them into a sum type.
+                wrap_either(&input.newer_type, call, arm - 1, n_arms)
+            } else {
+                call
             }
         });
+        let body = if let Some((validation, member)) = input.validation() {
+            emit_validated_body(
+                self.ident(),
+                (&validation, &member),
+                &trait_fn.sig,
+                &preds,
+                body,
+            )
+        } else {
+            body
+        };
+        // `#[must_use]` and `#[deprecated]` are not allowed on the items in trait
+        // impls. They take effect through the trait definition anyway.
+        let forwarded_attrs = trait_fn.attrs.iter().filter(|attr| {
+            [
+                "doc",
+                "track_caller",
+                "allow",
+                "warn",
+                "deny",
+                "forbid",
+                "expect",
+            ]
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+        });
+        // `inline` given to `#[implement]` takes precedence over `#[target]`
+        let inline = input.inline.or(input.target_inline);
+        let is_deprecated = trait_fn
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("deprecated"));
         quote! {
+            #(#forwarded_attrs)*
+            #(if let Some(Inline::Always) = &inline) { #[inline(always)] }
+            #(if let Some(Inline::Never) = &inline) { #[inline(never)] }
+            #(if inline.is_none()) { #[inline] }
+            #(if is_deprecated) { #[allow(deprecated)] }
             #{&sig.constness}
             #{&sig.asyncness}
             #{&sig.unsafety}
@@ -529,16 +900,68 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
         )
         .collect::<Punctuated<_, Token![,]>>();
         let pred_tys = self.get_predicate_types(&input.implementor);
+        // Locate the bounds on the inner types at the fields, so that the missing
+        // implementations on them are reported there.
+        let pred_spans = pred_tys.iter().map(|ty| ty.span()).collect::<Vec<_>>();
+        let unsized_ty = pred_tys
+            .iter()
+            .find(|ty| input.implementor.ref_kind.is_none() && is_unsized_type(ty, adt_generics))
+            .cloned();
+        let unsized_ty = unsized_ty.as_ref();
+        let validation = input.validation();
         let mut impl_generics_modified = impl_generics.clone();
+        // For `#[implement(ref Trait)]` and `#[implement(mut Trait)]`, implement the
+        // trait for references of the wrapper type.
+        let ref_lifetime = Lifetime::new(&format!("'__newer_type_ref_{nonce}"), Span::call_site());
+        let ref_prefix = input.implementor.ref_kind.as_ref().map(|kind| match kind {
+            RefKind::Ref(_) => quote!(& #ref_lifetime),
+            RefKind::Mut(kind) => {
+                if validation.is_some() {
+                    abort!(kind, "`mut` implementor cannot be used with `validate`"; note = "the inner value can be mutated without validation");
+                }
+                quote!(& #ref_lifetime mut)
+            }
+        });
+        let pred_tys = if let Some(ref_prefix) = &ref_prefix {
+            impl_generics_modified.insert(0, parse_quote!(#ref_lifetime));
+            pred_tys
+                .iter()
+                .map(|ty| parse_quote!(#ref_prefix #ty))
+                .collect()
+        } else {
+            pred_tys
+        };
+        let self_ty = quote!(#ref_prefix #{self.ident()} #{adt_generics.split_for_impl().1});
         let mut implr_args = trait_ty_generics.clone().unwrap_or_default();
         let items = input.trait_def.items.iter().map(|trait_item| match trait_item {
+            TraitItem::Fn(tfn) if unsized_ty.is_some() && has_self_sized_bound(&tfn.sig) => {
+                let ty = unsized_ty.unwrap();
+                if !is_unsized_type(ty, &Generics::default()) {
+                    abort!(
+                        &tfn.sig.ident,
+                        "`{}` requires `Self: Sized`, which cannot be forwarded to `?Sized` type parameter", &tfn.sig.ident;
+                        note = ty.span() => "`{}` may not be `Sized`", ty.to_token_stream();
+                        hint = "remove the `?Sized` bound"
+                    );
+                }
+                // Methods bounded by `Self: Sized` cannot be called on the unsized
+                // wrapper, so they can be omitted.
+                quote!()
+            }
             TraitItem::Fn(tfn) => {
+                if let Some(ty) = unsized_ty {
+                    check_unsized_receiver(&tfn.sig, ty);
+                }
+                let mut forwarded = tfn.clone();
+                if let Some(where_clause) = &mut forwarded.sig.generics.where_clause {
+                    bind_self_assoc_bounds(where_clause);
+                }
                 let tokens = self.emit_trait_fn(
                     &trait_path,
-                    &input.implementor,
-                    tfn.clone(),
+                    input,
+                    forwarded,
                     nonce,
-                    leaked_ty_visitor
+                    leaked_ty_visitor,
                 );
                 quote! {
                     #(if &tfn.sig.ident == "ne") {
@@ -553,6 +976,9 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
                 correct_assoc_tys.0.remove(&ttyp.ident);
                 let (impl_generics, ty_generics, where_clause) = ttyp.generics.split_for_impl();
                 if pred_tys.len() != 1 {
+                    if let Some(attr) = ttyp.attrs.iter().find(|attr| attr.path().is_ident("cfg")) {
+                        abort!(&input.implementor.path, "cannot implement this trait to enum"; note = attr.span() => "because the associated type is conditionally compiled");
+                    }
                     if ttyp.generics.params.is_empty() && where_clause.is_none() {
                         let new_tp = Ident::new(&format!("ASSOC_{}_{}", &ttyp.ident,nonce),ttyp.ident.span());
                         let assoc_ty = &ttyp.ident;
@@ -577,6 +1003,33 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
             ),
             o => abort!(o, "Not supported"),
         }).collect::<Vec<_>>();
+        // Mirror `#[cfg]` and `#[rustversion::..]` on the trait items
+        let items = items
+            .into_iter()
+            .zip(&input.trait_def.items)
+            .map(|(tokens, trait_item)| {
+                let attrs = match trait_item {
+                    TraitItem::Fn(TraitItemFn { attrs, .. })
+                    | TraitItem::Type(TraitItemType { attrs, .. })
+                    | TraitItem::Const(TraitItemConst { attrs, .. }) => &attrs[..],
+                    _ => &[],
+                };
+                let msrv = attrs.iter().find_map(crate::target::msrv_of);
+                quote! {
+                    #(if !tokens.is_empty()) {
+                        #(for attr in attrs.iter().filter(|attr| attr.path().is_ident("cfg") || crate::target::is_rustversion(attr))) {
+                            #attr
+                        }
+                        // Tell clippy that the item is used only with the
+                        // compilers since the version.
+                        #(if let Some(msrv) = &msrv) {
+                            #[clippy::msrv = #msrv]
+                        }
+                    }
+                    #tokens
+                }
+            })
+            .collect::<Vec<_>>();
         let detected_implicit_assoc_tys = correct_assoc_tys
             .0
             .iter()
@@ -631,27 +1084,34 @@ trait EmitImpl: Sized + Clone + template_quote::ToTokens {
         let pred_bounds = quote! {#path <#(for arg in &implr_args){#arg,} #(for (name, par) in &detected_implicit_assoc_tys) {#name = #par,}>};
         quote! {
             #[automatically_derived]
-            #{&input.trait_def.unsafety} impl < #impl_generics_modified > #trait_path for #{self.ident()} #{adt_generics.split_for_impl().1}
+            #{&input.trait_def.unsafety} impl < #impl_generics_modified > #trait_path for #self_ty
             where
                 #(#where_clause,)*
                 #(for st in &trait_supertraits) {
                     Self: #st,
                 }
-                #(#pred_tys: #pred_bounds),*
+                #(for (ty, span) in pred_tys.iter().zip(&pred_spans)) {
+                    #{locate_at(quote!(#ty: #pred_bounds), *span)},
+                }
             {
                 #(#items)*
             }
             #(if input.alternative.is_some()) {
                 #[automatically_derived]
-                unsafe impl < #impl_generics_modified > #{&input.implementor.path} for #{self.ident()} #{adt_generics.split_for_impl().1}
+                unsafe impl < #impl_generics_modified > #{&input.implementor.path} for #self_ty
                 where
                     #(#where_clause,)*
                     #(for st in &trait_supertraits) {
                         Self: #st,
                     }
-                    #(#pred_tys: #pred_bounds),*
+                    #(for (ty, span) in pred_tys.iter().zip(&pred_spans)) {
+                        #{locate_at(quote!(#ty: #pred_bounds), *span)},
+                    }
                 {}
             }
+            #(if input.assert_impl) {
+                #{emit_impl_assertion(input, &trait_path)}
+            }
         }
     }
 
@@ -678,8 +1138,8 @@ impl EmitImpl for ItemEnum {
         quote! {
             match #{&preds[0].1} {
                 #(for variant in &self.variants) {
-                    #(let (n, _) = find_pred_field(implementor, &variant.fields)) {
-                        Self::#{&variant.ident}
+                    #(let (n, _) = find_pred_field(Some(implementor), &variant.fields)) {
+                        #{&self.ident}::#{&variant.ident}
                         #(if let Fields::Named(_) = &variant.fields) {{
                             #(for (i, field) in variant.fields.iter().enumerate()) {
                                 #(if i == n) { #{&field.ident}: #pred_param }
@@ -712,12 +1172,7 @@ impl EmitImpl for ItemEnum {
     fn get_predicate_types(&self, implementor: &Implementor) -> Vec<Type> {
         self.variants
             .iter()
-            .map(|v| {
-                find_pred_field(&implementor.clone(), &v.fields)
-                    .1
-                    .ty
-                    .clone()
-            })
+            .map(|v| find_pred_field(Some(implementor), &v.fields).1.ty.clone())
             .collect()
     }
 }
@@ -732,16 +1187,16 @@ impl EmitImpl for ItemStruct {
         let pred_params = (0..preds.len())
             .map(|i| Ident::new(&format!("__newer_type_pred_param_{i}"), Span::call_site()))
             .collect::<Vec<_>>();
-        let (n, pred_field) = find_pred_field(implementor, &self.fields);
+        let (n, pred_field) = find_pred_field(Some(implementor), &self.fields);
         quote! {
             #(if let Fields::Named(_) = &self.fields) {
                 #(for ((_, pred_ident, _), pred_param) in preds.iter().zip(&pred_params)) {
-                    let Self {#{&pred_field.ident}: #pred_param, ..} = #pred_ident;
+                    let #{&self.ident} {#{&pred_field.ident}: #pred_param, ..} = #pred_ident;
                 }
             }
             #(if let Fields::Unnamed(_) = &self.fields) {
                 #(for ((_, pred_ident, _), pred_param) in preds.iter().zip(&pred_params)) {
-                    let Self (
+                    let #{&self.ident} (
                         #(for (i, _) in self.fields.iter().enumerate()), {
                             #(if i == n) {
                                 #pred_param
@@ -764,7 +1219,10 @@ impl EmitImpl for ItemStruct {
     }
 
     fn get_predicate_types(&self, implementor: &Implementor) -> Vec<Type> {
-        vec![find_pred_field(implementor, &self.fields).1.ty.clone()]
+        vec![find_pred_field(Some(implementor), &self.fields)
+            .1
+            .ty
+            .clone()]
     }
 }
 
@@ -836,6 +1294,15 @@ impl Input {
                     (GenericParam::Const(ConstParam { ident, .. }), GenericArgument::Const(c1)) => {
                         trait_modifier.const_map.insert(ident.clone(), c1.clone());
                     }
+                    // A const argument of a single identifier is parsed as a type
+                    (
+                        GenericParam::Const(ConstParam { ident, .. }),
+                        GenericArgument::Type(Type::Path(TypePath { qself: None, path })),
+                    ) if path.get_ident().is_some() => {
+                        trait_modifier
+                            .const_map
+                            .insert(ident.clone(), parse_quote!(#path));
+                    }
                     _ => {
                         abort!(implr_arg, "cannot assign this argument"; hint = tr_arg.span() => "param definition is here")
                     }
@@ -895,6 +1362,17 @@ impl Input {
         })
     }
 
+    fn validation(&self) -> Option<(Validation, Member)> {
+        match &self.adt {
+            Adt::Struct(item_struct) => {
+                let validation = self.adt.validation()?;
+                let (n, field) = find_pred_field(None, &item_struct.fields);
+                Some((validation, field_member(&field, n)))
+            }
+            Adt::Enum(_) => None,
+        }
+    }
+
     fn trait_ty_generics(&self) -> Option<Punctuated<GenericArgument, Token![,]>> {
         match &self
             .implementor
@@ -914,25 +1392,298 @@ impl Input {
 
     pub fn implement_internal(&self) -> TokenStream {
         let mut input = self.clone();
-        let nonce = crate::random();
+        let nonce = crate::nonce("implement", self);
         input.modify_implr_generics(nonce);
         input.modify_adt_generics(nonce);
         input.modify_trait_generics();
         let mut leaked_ty_visitor = input.make_leaked_ty_visitor(self.nonce);
-        match &input.adt {
+        let imp = match &input.adt {
             Adt::Enum(item_enum) => item_enum.emit_impl(&input, nonce, &mut leaked_ty_visitor),
             Adt::Struct(item_struct) => {
                 item_struct.emit_impl(&input, nonce, &mut leaked_ty_visitor)
             }
+        };
+        let output = quote! {
+            #imp
+            #{self.emit_supertraits()}
+        };
+        if self.debug || debug::is_enabled_by_env(&self.adt) {
+            let note = debug::dump(&self.adt, &self.implementor, &output);
+            quote!(#output #note)
+        } else {
+            output
+        }
+    }
+
+    /// Whether the supertrait `path` is declared alongside the trait, i.e. in
+    /// the module of the alternative trait if any, or in the module the
+    /// implementor refers to.
+    fn is_declared_alongside(&self, path: &Path) -> bool {
+        let module = module_of(path);
+        if let Some(alternative) = &self.alternative {
+            return module == module_of(alternative);
         }
+        let implementor_module = module_of(&self.implementor.path);
+        !implementor_module.is_empty()
+            && (module.ends_with(&implementor_module) || implementor_module.ends_with(&module))
+    }
+
+    /// Continue walking the supertraits for `with_supertraits`. Only the
+    /// supertraits declared alongside the trait are delegated, resolved by
+    /// replacing the last segment of the implementor path. The others are
+    /// skipped.
+    fn emit_supertraits(&self) -> TokenStream {
+        let Some(mut walk) = self.supertraits.clone() else {
+            return quote!();
+        };
+        walk.done.push(SupertraitWalk::key(&self.implementor));
+        let generic_args = generic_args_of(&self.trait_def.generics, &self.implementor);
+        for bound in &self.trait_def.supertraits {
+            let TypeParamBound::Trait(TraitBound {
+                modifier: TraitBoundModifier::None,
+                path,
+                ..
+            }) = bound
+            else {
+                continue;
+            };
+            let Some(mut seg) = path.segments.last().cloned() else {
+                continue;
+            };
+            if !self.is_declared_alongside(path) {
+                continue;
+            }
+            substitute_generic_args(&mut seg, &self.trait_def.generics, &generic_args);
+            let mut implementor = self.implementor.clone();
+            implementor.path.segments.pop();
+            implementor.path.segments.push(seg);
+            if !walk.contains(&implementor) {
+                walk.queue.push(implementor);
+            }
+        }
+        walk.emit_next(
+            &self.adt,
+            ImplementorOptions {
+                inline: self.inline,
+                with_supertraits: true,
+                assert_impl: self.assert_impl,
+                debug: self.debug,
+            },
+        )
     }
 }
 
-fn find_pred_field(implementor: &Implementor, fields: &Fields) -> (usize, Field) {
+/// Map the generic parameters of the trait (or trait bundle) to the arguments
+/// given to the implementor.
+pub fn generic_args_of(
+    generics: &Generics,
+    implementor: &Implementor,
+) -> HashMap<Ident, GenericArgument> {
+    let args = match implementor.path.segments.last().map(|seg| &seg.arguments) {
+        Some(PathArguments::AngleBracketed(args)) => args.args.iter().cloned().collect(),
+        _ => Vec::new(),
+    };
+    generics
+        .params
+        .iter()
+        .zip(args)
+        .filter_map(|(param, arg)| match param {
+            GenericParam::Type(TypeParam { ident, .. })
+            | GenericParam::Const(ConstParam { ident, .. }) => Some((ident.clone(), arg)),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+/// Substitute the generic parameters in the arguments of `seg`. Parameters
+/// omitted in the implementor are also omitted, expecting the same defaults.
+pub fn substitute_generic_args(
+    seg: &mut PathSegment,
+    generics: &Generics,
+    generic_args: &HashMap<Ident, GenericArgument>,
+) {
+    if let PathArguments::AngleBracketed(args) = &mut seg.arguments {
+        args.args = std::mem::take(&mut args.args)
+            .into_iter()
+            .filter_map(|arg| match &arg {
+                GenericArgument::Type(Type::Path(TypePath { qself: None, path }))
+                    if path.get_ident().map_or(false, |ident| {
+                        generics.type_params().any(|p| &p.ident == ident)
+                    }) =>
+                {
+                    generic_args.get(path.get_ident().unwrap()).cloned()
+                }
+                _ => Some(arg),
+            })
+            .collect();
+        if args.args.is_empty() {
+            seg.arguments = PathArguments::None;
+        }
+    }
+}
+
+/// Returns `true` if `ty` is known to be dynamically sized, namely `str`,
+/// slices, trait objects or type parameters with `?Sized` bound.
+pub fn is_unsized_type(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Slice(_) | Type::TraitObject(_) => true,
+        Type::Paren(TypeParen { elem, .. }) | Type::Group(TypeGroup { elem, .. }) => {
+            is_unsized_type(elem, generics)
+        }
+        Type::Path(TypePath { qself: None, path }) => {
+            let Some(ident) = path.get_ident() else {
+                return false;
+            };
+            if ident == "str" {
+                return true;
+            }
+            let is_maybe_sized = |bounds: &Punctuated<TypeParamBound, Token![+]>| {
+                bounds.iter().any(|bound| {
+                    matches!(
+                        bound,
+                        TypeParamBound::Trait(TraitBound {
+                            modifier: TraitBoundModifier::Maybe(_),
+                            ..
+                        })
+                    )
+                })
+            };
+            generics
+                .type_params()
+                .any(|p| &p.ident == ident && is_maybe_sized(&p.bounds))
+                || generics.where_clause.iter().any(|wc| {
+                    wc.predicates.iter().any(|pred| match pred {
+                        WherePredicate::Type(PredicateType {
+                            bounded_ty, bounds, ..
+                        }) => {
+                            matches!(bounded_ty, Type::Path(TypePath { qself: None, path }) if path.is_ident(ident))
+                                && is_maybe_sized(bounds)
+                        }
+                        _ => false,
+                    })
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Set the location of the tokens to `span` keeping the hygiene, so that the
+/// errors on the tokens are reported at `span`.
+pub fn locate_at(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = &tt {
+                let mut located = Group::new(group.delimiter(), locate_at(group.stream(), span));
+                located.set_span(group.span().located_at(span));
+                tt = TokenTree::Group(located);
+            } else {
+                tt.set_span(tt.span().located_at(span));
+            }
+            tt
+        })
+        .collect()
+}
+
+/// Whether `ty` mentions the type or const parameters of `generics`.
+fn mentions_generic_params(ty: &Type, generics: &Generics) -> bool {
+    struct MentionsParams<'a>(HashSet<&'a Ident>, bool);
+    impl Visit<'_> for MentionsParams<'_> {
+        fn visit_path(&mut self, i: &Path) {
+            self.1 |= i.leading_colon.is_none()
+                && i.segments
+                    .first()
+                    .map_or(false, |seg| self.0.contains(&seg.ident));
+            visit::visit_path(self, i)
+        }
+    }
+    let params = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+    let mut mentions = MentionsParams(params, false);
+    mentions.visit_type(ty);
+    mentions.1
+}
+
+/// Emit the assertions that the inner fields implement the trait for
+/// `assert_impl`, which are reported with the field and the trait. The
+/// assertions are declared with the generics of the ADT. The fields whose
+/// types mention its type or const parameters are skipped, since the trait is
+/// implemented only when they implement it, as well as the implementors with
+/// generics.
+fn emit_impl_assertion(input: &Input, trait_path: &Path) -> TokenStream {
+    if input.implementor.generics.is_some() || input.implementor.ref_kind.is_some() {
+        return quote!();
+    }
+    let adt_generics = input.adt.generics();
+    let (impl_generics, _, where_clause) = adt_generics.split_for_impl();
+    let trait_name = quote!(#{&input.implementor.path}).to_string();
+    let fields = match &input.adt {
+        Adt::Enum(item_enum) => item_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let (n, field) = find_pred_field(Some(&input.implementor), &variant.fields);
+                let member = field_member(&field, n).to_token_stream();
+                let name = format!(
+                    "`{}` of variant `{}::{}`",
+                    member, &item_enum.ident, &variant.ident
+                );
+                (field, name)
+            })
+            .collect::<Vec<_>>(),
+        Adt::Struct(item_struct) => {
+            let (n, field) = find_pred_field(Some(&input.implementor), &item_struct.fields);
+            let member = field_member(&field, n).to_token_stream();
+            let name = format!("`{}` of `{}`", member, &item_struct.ident);
+            vec![(field, name)]
+        }
+    }
+    .into_iter()
+    .filter(|(field, _)| !mentions_generic_params(&field.ty, adt_generics))
+    .collect::<Vec<_>>();
+    quote! {
+        #(for (field, name) in &fields) {
+            const _: () = {
+                #[diagnostic::on_unimplemented(
+                    message = #{format!("`{{Self}}` does not implement `{}`", &trait_name)},
+                    label = #{format!("required by `#[implement({})]`", &trait_name)},
+                    note = #{format!("`{}` is implemented by forwarding to the field {}", &trait_name, name)},
+                )]
+                trait __NewerTypeAssertImpl {}
+                impl<T: ?::core::marker::Sized + #trait_path> __NewerTypeAssertImpl for T {}
+                fn assert_impl<T: ?::core::marker::Sized + __NewerTypeAssertImpl>() {}
+                #[allow(dead_code)]
+                fn assert_field #impl_generics () #where_clause {
+                    #{locate_at(quote!(let _ = assert_impl::<#{&field.ty}>;), field.ty.span())}
+                }
+            };
+        }
+    }
+}
+
+pub fn field_member(field: &Field, index: usize) -> Member {
+    field
+        .ident
+        .clone()
+        .map(Member::Named)
+        .unwrap_or_else(|| Member::Unnamed(index.into()))
+}
+
+/// Find the field to be delegated. If `implementor` is `None`, the field is
+/// chosen without `#[implement(...)]` annotations on fields.
+pub fn find_pred_field(implementor: Option<&Implementor>, fields: &Fields) -> (usize, Field) {
     let pred_fields = fields
         .iter()
         .enumerate()
         .filter_map(|(i, field)| {
+            let implementor = implementor?;
             field
                 .attrs
                 .iter()
@@ -963,24 +1714,26 @@ fn find_pred_field(implementor: &Implementor, fields: &Fields) -> (usize, Field)
     } else {
         pred_fields
     };
+    let target = implementor.map_or_else(|| "the inner field".to_owned(), |i| i.to_string());
     match (pred_fields.len(), fields.len()) {
         (1, _) => pred_fields.into_iter().next().unwrap(),
         (0, 0) => abort!(
             fields,
             "No predicate found for implement {}",
-            implementor;
+            target;
             hint = "add any field here"
         ),
-        (0, _) => abort!(fields, "No predicate found for implement {}", implementor),
+        (0, _) => abort!(fields, "No predicate found for implement {}", target),
         (n, _) => abort!(
             fields,
             "Cannot implement {} for {} predicates",
-            implementor,
+            target,
             n;
             note = pred_fields[0].1.span() => "first predicate is here";
             note = pred_fields[1].1.span() => "second predicate is here";
             note =? (n > 2).then_some("and one or more predicates");
-            hint = "add #[implement({})] for any field", implementor;
+            hint =? implementor.map(|i| format!("add #[implement({})] for any field", i));
+            hint =? implementor.is_none().then_some("prefix the names of other fields with `_`");
         ),
     }
 }
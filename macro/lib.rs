@@ -2,15 +2,143 @@ use proc_macro::TokenStream as TokenStream1;
 use proc_macro_error::{abort, proc_macro_error};
 use syn::*;
 
+mod bundle;
+mod debug;
+mod diagnostics;
 mod implement;
 mod implement_internal;
+mod newtype;
 mod target;
 
-fn random() -> u64 {
-    use std::hash::{BuildHasher, Hasher};
-    std::collections::hash_map::RandomState::new()
-        .build_hasher()
-        .finish()
+/// Derive a nonce from the name of the crate being compiled, the location of
+/// the macro invocation and the tokens of the item (including the paths given
+/// as arguments), so that the expansion is reproducible across builds. The
+/// module path is not visible to proc macros, and the location is available
+/// only from Rust 1.88 and is shared by the items emitted from the same
+/// `macro_rules!`, so identical items in different modules may get the same
+/// nonce; the nonces handed out in this compilation are tracked and skipped to
+/// avoid such collisions.
+fn nonce(kind: &str, item: &impl template_quote::ToTokens) -> u64 {
+    use std::sync::Mutex;
+    static USED_NONCES: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+    // FNV-1a, which is stable across the toolchains unlike `DefaultHasher`.
+    fn hash(mut h: u64, bytes: &[u8]) -> u64 {
+        for b in bytes {
+            h ^= *b as u64;
+            h = h.wrapping_mul(0x100000001b3);
+        }
+        h
+    }
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    let span = proc_macro2::Span::call_site();
+    // Relative to the package, not to depend on where the sources are placed.
+    let mut file = span.file();
+    if let Ok(dir) = std::env::var("CARGO_MANIFEST_DIR") {
+        if let Ok(relative) = std::path::Path::new(&file).strip_prefix(dir) {
+            file = relative.to_string_lossy().into_owned();
+        }
+    }
+    let start = span.start();
+    let mut nonce = 0xcbf29ce484222325;
+    for part in [
+        crate_name.as_str(),
+        kind,
+        &file,
+        &format!("{}:{}", start.line, start.column),
+        &item.to_token_stream().to_string(),
+    ] {
+        nonce = hash(nonce, part.as_bytes());
+        nonce = hash(nonce, &[0xff]);
+    }
+    let mut used = USED_NONCES.lock().unwrap_or_else(|e| e.into_inner());
+    while used.contains(&nonce) {
+        nonce = hash(nonce, &[0xff]);
+    }
+    used.push(nonce);
+    nonce
+}
+
+/// Emit the helper macro `mac_name` and register it under the name of the
+/// trait with the visibility of the trait. Only public traits need
+/// `#[macro_export]`, which places the macro at the crate root; the others are
+/// kept in the scope of the module.
+fn emit_registration(
+    vis: &Visibility,
+    ident: &Ident,
+    mac_name: &Ident,
+    body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let is_pub = matches!(vis, Visibility::Public(_));
+    template_quote::quote! {
+        #[doc(hidden)]
+        #(if is_pub) { #[macro_export] }
+        macro_rules! #mac_name {
+            ($($t:tt)*) => { #body }
+        }
+        #[doc(hidden)]
+        #(if !is_pub) { #[allow(unused_imports)] }
+        #vis use #mac_name as #ident;
+    }
+}
+
+/// Version of the token protocol passed from `#[implement]`, `#[target]` and
+/// `#[target_bundle]` to the internal macros, which may be expanded by
+/// different versions of this crate in a dependency graph. Bump it on
+/// incompatible changes, keeping the previous version accepted.
+///
+/// Newer versions accept the tokens of the older ones, down to
+/// [`MIN_PROTOCOL_VERSION`]. The converse is guaranteed only for the tokens
+/// written in the layout of version 1, which is used as long as the options
+/// added later are not used (see [`protocol_header`]); otherwise, version 1
+/// fails to parse the tokens, and the versions since 2 report the newer
+/// version.
+const PROTOCOL_VERSION: u32 = 2;
+
+/// The oldest protocol version accepted. Version 1 is the protocol without the
+/// `version = N,` header.
+const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The `version = N,` header of the tokens, omitted if the tokens are written
+/// in the layout of version 1 (i.e. `v1_layout` is `true`), so that version 1
+/// without the header also accepts them.
+fn protocol_header(v1_layout: bool) -> proc_macro2::TokenStream {
+    if v1_layout {
+        proc_macro2::TokenStream::new()
+    } else {
+        template_quote::quote!(version = #PROTOCOL_VERSION,)
+    }
+}
+
+/// Parse the `version = N,` header of the tokens produced by `origin`, and
+/// check the compatibility.
+fn parse_protocol_version(input: parse::ParseStream, origin: &str) -> Result<u32> {
+    let fork = input.fork();
+    match fork.parse::<Ident>() {
+        Ok(ident) if ident == "version" && fork.peek(Token![=]) => (),
+        _ => return Ok(1),
+    }
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    let lit = input.parse::<LitInt>()?;
+    input.parse::<Token![,]>()?;
+    let version = lit.base10_parse::<u32>()?;
+    if version > PROTOCOL_VERSION {
+        abort!(
+            lit,
+            "{} is expanded by a newer version of `newer-type`", origin;
+            note = "the protocol version is {}, while this version supports up to {}", version, PROTOCOL_VERSION;
+            hint = "update `newer-type` to the same version in all the crates"
+        );
+    } else if version < MIN_PROTOCOL_VERSION {
+        abort!(
+            lit,
+            "{} is expanded by an older version of `newer-type`", origin;
+            note = "the protocol version is {}, while this version requires {} or later", version, MIN_PROTOCOL_VERSION;
+            hint = "update `newer-type` to the same version in all the crates"
+        );
+    }
+    Ok(version)
 }
 
 trait ResultExt {
@@ -41,6 +169,28 @@ pub fn implement(arg: TokenStream1, input: TokenStream1) -> TokenStream1 {
     implement::implement(&parse_macro_input!(arg), &parse_macro_input!(input)).into()
 }
 
+#[proc_macro_error]
+#[proc_macro_derive(Implement, attributes(implement))]
+pub fn derive_implement(input: TokenStream1) -> TokenStream1 {
+    implement::derive(&parse_macro_input!(input)).into()
+}
+
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn target_bundle(arg: TokenStream1, input: TokenStream1) -> TokenStream1 {
+    let input = bundle::bundle_def(parse_macro_input!(input));
+    bundle::target_bundle(parse_macro_input!(arg), input).into()
+}
+
+#[doc(hidden)]
+#[proc_macro_error]
+#[proc_macro]
+pub fn __implement_bundle(input: TokenStream1) -> TokenStream1 {
+    parse_macro_input!(input as bundle::Input)
+        .implement_bundle()
+        .into()
+}
+
 #[doc(hidden)]
 #[proc_macro_error]
 #[proc_macro]
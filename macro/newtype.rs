@@ -0,0 +1,331 @@
+use crate::implement::{Adt, Argument};
+use crate::implement_internal::{field_member, find_pred_field, is_unsized_type};
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use syn::spanned::Spanned;
+use syn::*;
+use template_quote::{quote, ToTokens};
+
+/// Construct the struct from `inner`, initializing other fields with
+/// `Default`.
+fn construct_from_inner(item_struct: &ItemStruct, n: usize) -> TokenStream {
+    quote! {
+        Self {
+            #(for (i, f) in item_struct.fields.iter().enumerate()) {
+                #{field_member(f, i)}:
+                #(if i == n) { inner }
+                #(else) { ::core::default::Default::default() },
+            }
+        }
+    }
+}
+
+/// Whether the type is a type parameter not covered by a local type, which
+/// conflicts with the blanket implementations of the conversion traits in
+/// `core`. References, `Box` and `Pin` do not cover the parameter, as they are
+/// `#[fundamental]`.
+fn is_uncovered_type_param(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Reference(TypeReference { elem, .. })
+        | Type::Paren(TypeParen { elem, .. })
+        | Type::Group(TypeGroup { elem, .. }) => is_uncovered_type_param(elem, generics),
+        Type::Path(TypePath { qself: None, path }) => {
+            let last = path.segments.last().unwrap();
+            match &last.arguments {
+                PathArguments::AngleBracketed(args)
+                    if (last.ident == "Box" || last.ident == "Pin") && args.args.len() == 1 =>
+                {
+                    match &args.args[0] {
+                        GenericArgument::Type(ty) => is_uncovered_type_param(ty, generics),
+                        _ => false,
+                    }
+                }
+                _ => is_type_param(ty, generics),
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_type_param(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path.get_ident().map_or(false, |ident| {
+            generics.type_params().any(|p| &p.ident == ident)
+        }),
+        _ => false,
+    }
+}
+
+/// Emit constructors, accessors and conversions for `#[implement(newtype)]`,
+/// `#[implement(validate = ...)]` and `#[implement(transparent)]`.
+pub fn emit_inherent_items(target_def: &Adt, arg: &Argument) -> TokenStream {
+    let newtype = arg.newtype.as_ref();
+    let validation = arg.validation.as_ref();
+    let transparent = arg.transparent.as_ref();
+    let (span, name) = match (newtype, validation, transparent) {
+        (Some(newtype), _, _) => (newtype.span(), "newtype"),
+        (_, Some(validation), _) => (validation.check.span(), "validate"),
+        (_, _, Some(transparent)) => (transparent.span(), "transparent"),
+        (None, None, None) => return quote!(),
+    };
+    let item_struct = match target_def {
+        Adt::Struct(item_struct) => item_struct,
+        Adt::Enum(item_enum) => {
+            abort!(
+                span,
+                "`{}` is only supported for structs", name;
+                note = item_enum.ident.span() => "`{}` is an enum", &item_enum.ident
+            )
+        }
+    };
+    let (n, field) = find_pred_field(None, &item_struct.fields);
+    let member = field_member(&field, n);
+    let inner_ty = &field.ty;
+    let is_unsized = is_unsized_type(inner_ty, &item_struct.generics);
+    if let (Some(validation), true) = (validation, is_unsized) {
+        abort!(
+            &validation.check,
+            "`validate` is not supported for dynamically sized inner types";
+            note = inner_ty.span() => "`{}` is not `Sized`", inner_ty.to_token_stream()
+        );
+    }
+    if let (Some(validation), Some(transparent)) = (validation, transparent) {
+        abort!(
+            transparent,
+            "`transparent` cannot be used with `validate`";
+            note = validation.check.span() => "casting references would bypass the validation"
+        );
+    }
+    let sized = !is_unsized;
+    let newer_type = arg.newer_type();
+    let vis = &item_struct.vis;
+    let field_hidden = is_field_hidden(item_struct, &field);
+    let exposing_vis = if field_hidden { &field.vis } else { vis };
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    let self_ty = quote!(#{&item_struct.ident} #ty_generics);
+    let error_ty = quote!(#newer_type::ValidationError<#inner_ty>);
+    let constructed = construct_from_inner(item_struct, n);
+    quote! {
+        impl #impl_generics #self_ty #where_clause {
+            #(if let (Some(validation), true) = (validation, sized)) {
+                /// Creates a new value, or returns an error if the inner value is
+                /// rejected by the validation function.
+                #vis fn new(inner: #inner_ty) -> ::core::result::Result<Self, #error_ty> {
+                    if #{&validation.check}(&inner) {
+                        ::core::result::Result::Ok(#constructed)
+                    } else {
+                        ::core::result::Result::Err(#newer_type::ValidationError::new(inner))
+                    }
+                }
+            }
+            #(else if sized && (newtype.is_some() || validation.is_some())) {
+                /// Creates a new value from the inner value.
+                #vis fn new(inner: #inner_ty) -> Self {
+                    #constructed
+                }
+            }
+            #(if newtype.is_some()) {
+                #(if sized) {
+                    /// Unwraps the inner value.
+                    #exposing_vis fn into_inner(self) -> #inner_ty {
+                        self.#member
+                    }
+                }
+
+                /// Gets a reference to the inner value.
+                #exposing_vis fn as_inner(&self) -> &#inner_ty {
+                    &self.#member
+                }
+
+                #(if validation.is_none()) {
+                    /// Gets a mutable reference to the inner value.
+                    #exposing_vis fn as_inner_mut(&mut self) -> &mut #inner_ty {
+                        &mut self.#member
+                    }
+                }
+            }
+            #(if transparent.is_some()) {
+                /// Views a reference to the inner value as a reference to the wrapper.
+                #vis fn from_ref(inner: &#inner_ty) -> &Self {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                    unsafe { &*(inner as *const #inner_ty as *const Self) }
+                }
+
+                /// Views a mutable reference to the inner value as a mutable
+                /// reference to the wrapper.
+                #vis fn from_mut(inner: &mut #inner_ty) -> &mut Self {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                    unsafe { &mut *(inner as *mut #inner_ty as *mut Self) }
+                }
+
+                #(if sized) {
+                    /// Views a slice of the inner values as a slice of the wrapper.
+                    #vis fn from_slice(inner: &[#inner_ty]) -> &[Self] {
+                        // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                        unsafe { &*(inner as *const [#inner_ty] as *const [Self]) }
+                    }
+
+                    /// Views a mutable slice of the inner values as a mutable slice
+                    /// of the wrapper.
+                    #vis fn from_slice_mut(inner: &mut [#inner_ty]) -> &mut [Self] {
+                        // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                        unsafe { &mut *(inner as *mut [#inner_ty] as *mut [Self]) }
+                    }
+                }
+            }
+        }
+
+        #(if transparent.is_some()) {
+            #newer_type::__with_std! {
+                impl #impl_generics #self_ty #where_clause {
+                    /// Converts a boxed inner value into a boxed wrapper.
+                    #vis fn from_boxed(
+                        inner: #newer_type::__private::Box<#inner_ty>,
+                    ) -> #newer_type::__private::Box<Self> {
+                        let ptr = #newer_type::__private::Box::into_raw(inner);
+                        // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                        unsafe { #newer_type::__private::Box::from_raw(ptr as *mut Self) }
+                    }
+
+                    /// Converts a boxed wrapper into a boxed inner value.
+                    #vis fn into_boxed_inner(
+                        self: #newer_type::__private::Box<Self>,
+                    ) -> #newer_type::__private::Box<#inner_ty> {
+                        let ptr = #newer_type::__private::Box::into_raw(self);
+                        // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                        unsafe { #newer_type::__private::Box::from_raw(ptr as *mut #inner_ty) }
+                    }
+                }
+            }
+        }
+
+        #(if !sized || (newtype.is_none() && validation.is_none())) {}
+        #(else if validation.is_some() && is_uncovered_type_param(inner_ty, &item_struct.generics)) {}
+        #(else if validation.is_some()) {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::TryFrom<#inner_ty> for #self_ty #where_clause {
+                type Error = #error_ty;
+
+                fn try_from(inner: #inner_ty) -> ::core::result::Result<Self, Self::Error> {
+                    Self::new(inner)
+                }
+            }
+        }
+        #(else) {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#inner_ty> for #self_ty #where_clause {
+                fn from(inner: #inner_ty) -> Self {
+                    Self::new(inner)
+                }
+            }
+        }
+
+        #(if sized && newtype.is_some() && !field_hidden && !is_uncovered_type_param(inner_ty, &item_struct.generics)) {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#self_ty> for #inner_ty #where_clause {
+                fn from(value: #self_ty) -> Self {
+                    value.#member
+                }
+            }
+        }
+    }
+}
+
+/// Rank of the visibility, where the larger one is more visible. `pub(in ..)`
+/// is ranked as `pub(super)`.
+fn visibility_rank(vis: &Visibility) -> u8 {
+    match vis {
+        Visibility::Public(_) => 3,
+        Visibility::Restricted(VisRestricted { path, .. }) if path.is_ident("crate") => 2,
+        Visibility::Restricted(VisRestricted { path, .. }) if path.is_ident("self") => 0,
+        Visibility::Restricted(_) => 1,
+        Visibility::Inherited => 0,
+    }
+}
+
+/// Whether the inner field is less visible than the struct. The items exposing
+/// the field are then given the visibility of the field, and the traits
+/// exposing it are not implemented.
+fn is_field_hidden(item_struct: &ItemStruct, field: &Field) -> bool {
+    visibility_rank(&field.vis) < visibility_rank(&item_struct.vis)
+}
+
+/// Emit the implementation of `newer_type::Newtype`, for structs with single
+/// field or structs with `#[implement(newtype)]`. The trait exposes the inner
+/// field as well as the struct, so it is not implemented if the field is less
+/// visible.
+pub fn emit_newtype_impl(target_def: &Adt, arg: &Argument) -> TokenStream {
+    let item_struct = match target_def {
+        Adt::Struct(item_struct) if arg.validation.is_none() => item_struct,
+        _ => return quote!(),
+    };
+    let (n, field) = match (item_struct.fields.len(), &arg.newtype) {
+        (_, Some(_)) => find_pred_field(None, &item_struct.fields),
+        (1, None) => (0, item_struct.fields.iter().next().cloned().unwrap()),
+        _ => return quote!(),
+    };
+    if is_unsized_type(&field.ty, &item_struct.generics) || is_field_hidden(item_struct, &field) {
+        return quote!();
+    }
+    let member = field_member(&field, n);
+    let inner_ty = &field.ty;
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #{arg.newer_type()}::Newtype for #{&item_struct.ident} #ty_generics #where_clause {
+            type Inner = #inner_ty;
+
+            fn from_inner(inner: Self::Inner) -> Self {
+                #{construct_from_inner(item_struct, n)}
+            }
+
+            fn into_inner(self) -> Self::Inner {
+                self.#member
+            }
+
+            fn inner(&self) -> &Self::Inner {
+                &self.#member
+            }
+
+            fn inner_mut(&mut self) -> &mut Self::Inner {
+                &mut self.#member
+            }
+        }
+    }
+}
+
+/// Add `#[repr(transparent)]` to the struct for `#[implement(transparent)]`,
+/// or verify that it is already present.
+pub fn add_repr_transparent(target_def: &mut Adt, transparent: &Ident) {
+    let item_struct = match target_def {
+        Adt::Struct(item_struct) => item_struct,
+        Adt::Enum(item_enum) => abort!(
+            transparent,
+            "`transparent` is only supported for structs";
+            note = item_enum.ident.span() => "`{}` is an enum", &item_enum.ident
+        ),
+    };
+    for attr in item_struct
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let is_transparent = attr
+            .parse_args::<Ident>()
+            .map_or(false, |repr| repr == "transparent");
+        if !is_transparent {
+            abort!(
+                attr,
+                "`#[implement(transparent)]` conflicts with this representation";
+                note = transparent.span() => "`transparent` requires `#[repr(transparent)]`"
+            );
+        }
+    }
+    if !item_struct
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("repr"))
+    {
+        item_struct.attrs.push(parse_quote!(#[repr(transparent)]));
+    }
+}
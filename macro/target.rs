@@ -1,13 +1,19 @@
-use proc_macro2::{Span, TokenStream};
+use crate::implement::Inline;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_error::abort;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
 use syn::*;
-use template_quote::quote;
-use type_leak::{Leaker, NotInternableError, Referrer};
+use template_quote::{quote, quote_spanned};
+use type_leak::{CheckResult, Leaker, Referrer};
 
 pub struct Argument {
     alternative: Option<Path>,
     newer_type: Path,
     repeater: Option<Path>,
+    inline: Option<Inline>,
+    complete: Option<Ident>,
 }
 
 impl syn::parse::Parse for Argument {
@@ -15,8 +21,18 @@ impl syn::parse::Parse for Argument {
         let mut alternative = None;
         let mut newer_type = parse_quote!(::newer_type);
         let mut repeater = None;
+        let mut inline = None;
+        let mut complete = None;
 
         while !input.is_empty() {
+            if let Some(ident) = crate::implement::parse_flag(input, "complete") {
+                complete = Some(ident);
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+                continue;
+            }
             let ident = input.parse::<Ident>()?;
             input.parse::<token::Eq>()?;
             match ident.to_string().as_str() {
@@ -29,6 +45,9 @@ impl syn::parse::Parse for Argument {
                 "repeater" => {
                     repeater = Some(input.parse()?);
                 }
+                "inline" => {
+                    inline = Some(input.parse()?);
+                }
                 _ => {
                     return Err(Error::new_spanned(&ident, "Unsupported argument"));
                 }
@@ -42,16 +61,308 @@ impl syn::parse::Parse for Argument {
             alternative,
             newer_type,
             repeater,
+            inline,
+            complete,
         })
     }
 }
 
+/// Names in the prelude, which are resolved in the same way in the crate
+/// implementing the trait.
+const PRELUDE: &[&str] = &[
+    "Copy",
+    "Send",
+    "Sized",
+    "Sync",
+    "Unpin",
+    "Drop",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "AsMut",
+    "AsRef",
+    "From",
+    "Into",
+    "DoubleEndedIterator",
+    "ExactSizeIterator",
+    "Extend",
+    "IntoIterator",
+    "Iterator",
+    "FromIterator",
+    "Clone",
+    "Default",
+    "Eq",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Option",
+    "Result",
+    "Box",
+    "String",
+    "Vec",
+];
+
+/// Collects the types in the trait signatures which should be carried by the
+/// repeater, because they are referred relatively to the module of the trait.
+///
+/// The largest internable types are collected. The types which cannot be
+/// interned as a whole (e.g. which contain the generic parameters of the
+/// methods or elided lifetimes) are decomposed, and their constituents are
+/// collected instead.
+struct LeakCollector {
+    leaker: Leaker,
+    generics_base: Generics,
+    generics: Generics,
+    types: Vec<Type>,
+}
+
+impl LeakCollector {
+    fn with_generics(&mut self, generics: &Generics, f: impl FnOnce(&mut Self)) {
+        let saved = self.generics.clone();
+        self.generics.params.extend(generics.params.iter().cloned());
+        f(self);
+        self.generics = saved;
+    }
+
+    /// Returns `true` if the type refers to the generic parameters of the
+    /// method, like `I::Item`, which cannot appear in the repeater.
+    fn has_method_generics(&self, ty: &Type) -> bool {
+        struct Checker<'a>(&'a [&'a Ident], bool);
+        impl Visit<'_> for Checker<'_> {
+            fn visit_path(&mut self, i: &Path) {
+                if i.leading_colon.is_none() {
+                    if let Some(first) = i.segments.first() {
+                        self.1 |= self.0.contains(&&first.ident);
+                    }
+                }
+                syn::visit::visit_path(self, i)
+            }
+        }
+        let method_params: Vec<_> = self
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .filter(|ident| !self.generics_base.type_params().any(|p| &p.ident == *ident))
+            .collect();
+        let mut checker = Checker(&method_params, false);
+        checker.visit_type(ty);
+        checker.1
+    }
+
+    /// Abort if `path` cannot be resolved from the implementing site, where the
+    /// path is emitted as is.
+    fn check_reachable(&self, path: &Path, what: &str) {
+        if path.leading_colon.is_some() {
+            return;
+        }
+        let Some(first) = path.segments.first() else {
+            return;
+        };
+        let first_ident = first.ident.to_string();
+        let is_generic_param = self.generics.params.iter().any(|param| match param {
+            GenericParam::Type(TypeParam { ident, .. })
+            | GenericParam::Const(ConstParam { ident, .. }) => ident == &first.ident,
+            GenericParam::Lifetime(_) => false,
+        });
+        let is_prelude = path.segments.len() == 1 && PRELUDE.contains(&first_ident.as_str());
+        if matches!(first_ident.as_str(), "crate" | "$crate" | "Self")
+            || is_generic_param
+            || is_prelude
+        {
+            return;
+        }
+        let path_str = quote!(#path).to_string().replace(' ', "");
+        abort!(
+            path,
+            "`{}` is not reachable from where the trait is implemented", path_str;
+            note = "{} is emitted as is in the implementation, but it is relative to the module of the trait", what;
+            hint = "refer to it with `crate::` path or an absolute path"
+        )
+    }
+}
+
+/// Returns `true` if the type has elided or anonymous lifetimes, which cannot
+/// appear in the repeater. A trait object without lifetime bound is also the
+/// case, since its default lifetime is changed in the repeater.
+fn has_implicit_lifetime(ty: &Type) -> bool {
+    struct Checker(bool);
+    impl Visit<'_> for Checker {
+        fn visit_type_reference(&mut self, i: &TypeReference) {
+            self.0 |= i.lifetime.is_none();
+            syn::visit::visit_type_reference(self, i)
+        }
+        fn visit_lifetime(&mut self, i: &Lifetime) {
+            self.0 |= i.ident == "_";
+        }
+        // Elided lifetimes in function pointers are late-bound.
+        fn visit_type_bare_fn(&mut self, _: &TypeBareFn) {}
+    }
+    let mut checker = Checker(false);
+    checker.visit_type(ty);
+    checker.0
+        || matches!(ty, Type::TraitObject(TypeTraitObject { bounds, .. })
+            if !bounds.iter().any(|b| matches!(b, TypeParamBound::Lifetime(_))))
+}
+
+impl Visit<'_> for LeakCollector {
+    fn visit_attribute(&mut self, _: &Attribute) {}
+
+    fn visit_trait_item_fn(&mut self, i: &TraitItemFn) {
+        // The default bodies are not emitted in the implementation.
+        self.with_generics(&i.sig.generics, |this| this.visit_signature(&i.sig));
+    }
+
+    fn visit_trait_item_type(&mut self, i: &TraitItemType) {
+        self.with_generics(&i.generics, |this| {
+            syn::visit::visit_trait_item_type(this, i)
+        });
+    }
+
+    fn visit_trait_item_const(&mut self, i: &TraitItemConst) {
+        self.with_generics(&i.generics, |this| {
+            syn::visit::visit_trait_item_const(this, i)
+        });
+    }
+
+    fn visit_receiver(&mut self, i: &Receiver) {
+        if i.colon_token.is_some() {
+            self.visit_type(&i.ty);
+        }
+    }
+
+    fn visit_type(&mut self, i: &Type) {
+        match self.leaker.check(&self.generics_base, &self.generics, i) {
+            Ok(CheckResult::MustIntern(_) | CheckResult::MustInternOrInherit(_))
+                if !has_implicit_lifetime(i) && !self.has_method_generics(i) =>
+            {
+                if !self.types.contains(i) {
+                    self.types.push(i.clone());
+                }
+            }
+            Ok(CheckResult::Neutral | CheckResult::MustNotIntern(_)) => (),
+            // Decompose the type into the constituents
+            _ => syn::visit::visit_type(self, i),
+        }
+    }
+
+    fn visit_type_path(&mut self, i: &TypePath) {
+        self.check_reachable(
+            &i.path,
+            "the type with generic parameters of the method or elided lifetimes",
+        );
+        syn::visit::visit_type_path(self, i)
+    }
+
+    fn visit_trait_bound(&mut self, i: &TraitBound) {
+        self.check_reachable(&i.path, "the trait bound");
+        syn::visit::visit_trait_bound(self, i)
+    }
+
+    fn visit_expr_path(&mut self, i: &ExprPath) {
+        self.check_reachable(&i.path, "the value");
+        syn::visit::visit_expr_path(self, i)
+    }
+}
+
+fn collect_leaked_types(input: &ItemTrait) -> Referrer {
+    let mut collector = LeakCollector {
+        leaker: Leaker::with_generics(input.generics.clone()),
+        generics_base: input.generics.clone(),
+        generics: input.generics.clone(),
+        types: Vec::new(),
+    };
+    collector.visit_item_trait(input);
+    let types = &collector.types;
+    parse2(quote!((#(for ty in types), { #ty }))).unwrap()
+}
+
+/// Replace `crate` in the paths with `$crate`, to resolve them from the
+/// implementing site in the other crates.
+fn replace_crate_keyword(tokens: TokenStream) -> TokenStream {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    let is_punct = |tt: Option<&TokenTree>, c: char| matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == c);
+    let mut ret = Vec::new();
+    for (n, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_crate_keyword(group.stream()),
+                );
+                new_group.set_span(group.span());
+                ret.push(TokenTree::Group(new_group));
+                continue;
+            }
+            // `crate` followed by `::`, which is not `pub(crate)` nor `$crate`
+            TokenTree::Ident(ident)
+                if ident == "crate"
+                    && !is_punct(n.checked_sub(1).map(|m| &tokens[m]), '$')
+                    && is_punct(tokens.get(n + 1), ':') =>
+            {
+                ret.push(TokenTree::Punct(proc_macro2::Punct::new(
+                    '$',
+                    proc_macro2::Spacing::Alone,
+                )));
+            }
+            _ => (),
+        }
+        ret.push(tt.clone());
+    }
+    ret.into_iter().collect()
+}
+
+/// Replace `Self` in the leaked type with the type parameter of the repeater
+/// implementation, since `Self` there refers to the implementor of the
+/// repeater.
+fn replace_self_type(ty: &Type, self_type: &Ident) -> Type {
+    struct Replacer<'a>(&'a Ident);
+    impl VisitMut for Replacer<'_> {
+        fn visit_type_path_mut(&mut self, i: &mut TypePath) {
+            if i.qself.is_none() {
+                if let Some(first) = i.path.segments.first_mut() {
+                    if first.ident == "Self" && first.arguments.is_none() {
+                        first.ident = self.0.clone();
+                    }
+                }
+            }
+            syn::visit_mut::visit_type_path_mut(self, i)
+        }
+    }
+    let mut ty = ty.clone();
+    Replacer(self_type).visit_type_mut(&mut ty);
+    ty
+}
+
+/// Removes the defaults of the generic parameters, which are not allowed in
+/// the impls.
+fn without_defaults(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Lifetime(_) => (),
+        }
+    }
+    generics
+}
+
 fn emit_repeater_impl(
     input: &ItemTrait,
     referrer: &Referrer,
     repeater: &Option<Path>,
     nonce: u64,
-) -> (Path, TokenStream) {
+) -> (TokenStream, TokenStream) {
     let self_type = Ident::new(&format!("__NewerTypeSelf{nonce}"), Span::call_site());
     let (_, ty_generics, where_clause) = input.generics.split_for_impl();
     let generic_args = input
@@ -70,30 +381,34 @@ fn emit_repeater_impl(
             }
         })
         .collect::<Vec<_>>();
-    let mut impl_generics = input.generics.params.clone();
-    impl_generics.push(GenericParam::Type(parse_quote!(#self_type)));
+    let mut impl_generics = without_defaults(&input.generics).params;
+    impl_generics.push(GenericParam::Type(
+        parse_quote!(#self_type: ?::core::marker::Sized),
+    ));
     let encoded_generics = type_leak::encode_generics_to_ty(&generic_args);
-    let (repeater, repeater_def) = if let Some(repeater) = repeater {
-        (repeater.clone(), quote!())
+    // Without `repeater` argument, the repeater defined by
+    // `newer_type::repeater!()` at the crate root is used. It is referred with
+    // `$crate` in the macro to be expanded in the other crates. The traits not
+    // visible from the other crates use the crate-visible one, which can carry
+    // `pub(crate)` types.
+    let mut repeater_check = quote!();
+    let (repeater, repeater_in_macro) = if let Some(repeater) = repeater {
+        (repeater.clone(), replace_crate_keyword(quote!(#repeater)))
     } else {
-        let repeater_ident = Ident::new(
-            &format!("give_repeater_argument_to_this_target_attribute_macro_{nonce}"),
-            Span::call_site(),
-        );
-        (
-            parse_quote!(#repeater_ident),
-            quote! {
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                #{&input.vis} trait #repeater_ident<
-                    const TRAIT_ID: ::core::primitive::u64,
-                    const NTH: ::core::primitive::usize,
-                    T: ?::core::marker::Sized
-                > {
-                    type Type: ?::core::marker::Sized;
-                }
-            },
-        )
+        // Without `newer_type::repeater!()`, this import fails with the name
+        // telling how to fix it.
+        if !referrer.is_empty() {
+            repeater_check = quote! {
+                #[allow(unused_imports)]
+                use crate::call_newer_type_repeater_macro_at_the_crate_root as _;
+            };
+        }
+        let ident = if matches!(input.vis, Visibility::Public(_)) {
+            Ident::new("__NewerTypeRepeater", Span::call_site())
+        } else {
+            Ident::new("__NewerTypeCrateRepeater", Span::call_site())
+        };
+        (parse_quote!(crate::#ident), quote!($crate::#ident))
     };
     let ret: TokenStream = referrer
         .iter()
@@ -102,36 +417,188 @@ fn emit_repeater_impl(
             quote! {
                 impl < #impl_generics > #repeater<#nonce, #n, #encoded_generics> for #self_type
                 where
-                    Self: #{&input.ident} #ty_generics,
+                    #self_type: #{&input.ident} #ty_generics,
                     #{where_clause.map(|wc| &wc.predicates)}
                 {
-                    type Type = #ty;
+                    // Located at the type, to report the private types there
+                    #{quote_spanned!(ty.span() => type Type = #{replace_self_type(ty, &self_type)};)}
                 }
             }
         })
         .collect();
-    (repeater, quote!(#repeater_def #ret))
+    (repeater_in_macro, quote!(#repeater_check #ret))
+}
+
+/// Whether the attribute is one of `rustversion`, which is forwarded to the
+/// implementations like `#[cfg]`.
+pub fn is_rustversion(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .iter()
+        .any(|seg| seg.ident == "rustversion")
 }
 
-pub fn target(arg: Argument, input: ItemTrait) -> TokenStream {
-    let mut input_cloned = input.clone();
-    for item in input_cloned.items.iter_mut() {
-        if let TraitItem::Fn(trait_item_fn) = item {
-            trait_item_fn.default = None;
-            trait_item_fn.semi_token = Some(Token![;](Span::call_site()));
+/// The version given to `#[rustversion::since(..)]`, which is the minimum
+/// supported Rust version of the item.
+pub fn msrv_of(attr: &Attribute) -> Option<String> {
+    if !is_rustversion(attr) || attr.path().segments.last().unwrap().ident != "since" {
+        return None;
+    }
+    let version = attr
+        .meta
+        .require_list()
+        .ok()?
+        .tokens
+        .to_string()
+        .replace(' ', "");
+    version
+        .split('.')
+        .all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        .then_some(version)
+}
+
+/// Replace `rustversion` in `#[rustversion::since(..)]` on the trait items with
+/// the one re-exported from `newer_type`, because the attributes are evaluated
+/// in the implementing crates, which may not depend on `rustversion`.
+fn resolve_rustversion(input: &mut ItemTrait, newer_type: &Path) {
+    for item in &mut input.items {
+        let attrs = match item {
+            TraitItem::Fn(TraitItemFn { attrs, .. })
+            | TraitItem::Type(TraitItemType { attrs, .. })
+            | TraitItem::Const(TraitItemConst { attrs, .. }) => attrs,
+            _ => continue,
+        };
+        for attr in attrs.iter_mut() {
+            let path = match &mut attr.meta {
+                Meta::Path(path) => path,
+                Meta::List(MetaList { path, .. }) => path,
+                Meta::NameValue(MetaNameValue { path, .. }) => path,
+            };
+            if path.leading_colon.is_none()
+                && path.segments.len() == 2
+                && path.segments[0].ident == "rustversion"
+            {
+                let name = &path.segments[1];
+                *path = parse_quote!(#newer_type::__private::rustversion::#name);
+            }
         }
     }
-    let nonce = crate::random();
+}
+
+/// Emits a never-used trait whose methods are implemented by calling the
+/// methods of `alternative`, which fails to compile when the signature
+/// declared in the shim is not compatible with the real one.
+fn emit_alternative_check(input: &ItemTrait, alternative: &Path) -> TokenStream {
+    let self_type = Ident::new("__NewerTypeSelf", Span::call_site());
+    let mut impl_generics = without_defaults(&input.generics);
+    impl_generics
+        .params
+        .push(GenericParam::Type(parse_quote!(#self_type)));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    // `async fn` and return position `impl Trait` are not supported in the
+    // traits with the minimum supported Rust version, so they are skipped.
+    let sigs: Vec<_> = input
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Fn(item)
+                if item.sig.asyncness.is_none()
+                    && !matches!(&item.sig.output, ReturnType::Type(_, ty) if contains_impl_trait(ty)) =>
+            {
+                let mut sig = item.sig.clone();
+                let mut args = Vec::new();
+                for (n, arg) in sig.inputs.iter_mut().enumerate() {
+                    match arg {
+                        FnArg::Receiver(receiver) => {
+                            let self_token = &receiver.self_token;
+                            args.push(quote!(#self_token))
+                        }
+                        FnArg::Typed(pat_type) => {
+                            let ident =
+                                Ident::new(&format!("__newer_type_arg_{n}"), Span::call_site());
+                            *pat_type.pat = parse_quote!(#ident);
+                            args.push(quote!(#ident));
+                        }
+                    }
+                }
+                let cfgs: Vec<_> = item
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("cfg") || is_rustversion(attr))
+                    .collect();
+                Some((cfgs, sig, args))
+            }
+            _ => None,
+        })
+        .collect();
+    quote! {
+        #[allow(clippy::all, dead_code, unused_qualifications)]
+        const _: () = {
+            trait __NewerTypeCheck #{&input.generics}: #alternative #where_clause {
+                #(for (cfgs, sig, _) in &sigs) {
+                    #(#cfgs)* #sig;
+                }
+            }
+            impl #impl_generics __NewerTypeCheck #ty_generics for #self_type
+            where
+                #self_type: #alternative,
+                #{where_clause.map(|wc| &wc.predicates)}
+                // The methods taking the unsized parameters by value cannot
+                // have their bodies
+                #(for param in input.generics.type_params()) {
+                    #{&param.ident}: ::core::marker::Sized,
+                }
+            {
+                #(for (cfgs, sig, args) in &sigs) {
+                    #(#cfgs)*
+                    #[allow(unused_unsafe)]
+                    #sig {
+                        // Located at the declared method, to report the
+                        // mismatch there
+                        #{crate::implement_internal::locate_at(
+                            quote!(unsafe { <#self_type as #alternative>::#{&sig.ident}(#(#args),*) }),
+                            sig.ident.span(),
+                        )}
+                    }
+                }
+            }
+        };
+    }
+}
+
+fn contains_impl_trait(ty: &Type) -> bool {
+    struct Finder(bool);
+    impl<'ast> Visit<'ast> for Finder {
+        fn visit_type_impl_trait(&mut self, _: &'ast TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+    let mut finder = Finder(false);
+    finder.visit_type(ty);
+    finder.0
+}
+
+pub fn target(arg: Argument, mut input: ItemTrait) -> TokenStream {
+    let nonce = crate::nonce("target", &input);
+    resolve_rustversion(&mut input, &arg.newer_type);
     let crate_path = &arg.newer_type;
-    let mut leaker = Leaker::from_trait(
-        &input_cloned,
-    )
-    .unwrap_or_else(|NotInternableError(span)| abort!(span, "cannot intern this element"; hint = "use absolute path instead"));
-    leaker.reduce_roots();
-    let referrer = leaker.finish();
+    let referrer = collect_leaked_types(&input);
     let repeater = &arg.repeater;
     let (repeater_path, repeater_impl) = emit_repeater_impl(&input, &referrer, repeater, nonce);
     let mut output = input.clone();
+    let mut alternative_check = None;
+    let mut complete_check = TokenStream::new();
+    if let Some(complete) = &arg.complete {
+        let Some(alternative) = &arg.alternative else {
+            abort!(
+                complete,
+                "`complete` requires `alternative`";
+                note = "only the provided methods of the alternative trait are checked"
+            );
+        };
+        complete_check = crate::diagnostics::check_complete(complete, alternative, &input);
+    }
     if let Some(mut alternative) = arg.alternative.clone() {
         let last_seg = alternative.segments.iter_mut().next_back().unwrap();
         let mut args = AngleBracketedGenericArguments {
@@ -154,6 +621,7 @@ pub fn target(arg: Argument, input: ItemTrait) -> TokenStream {
             }
         }
         last_seg.arguments = PathArguments::AngleBracketed(args);
+        alternative_check = Some(emit_alternative_check(&input, &alternative));
         output.colon_token = Some(Token![:](Span::call_site()));
         output.supertraits.push(TypeParamBound::Trait(TraitBound {
             paren_token: Default::default(),
@@ -170,28 +638,38 @@ pub fn target(arg: Argument, input: ItemTrait) -> TokenStream {
             .push(parse_quote!(#[doc = " should be implemented by [`newer_type::implement`]"]));
     }
 
+    output
+        .items
+        .push(crate::diagnostics::marker_method(&input.ident));
+
     let temporal_mac_name = Ident::new(&format!("__newer_type_macro__{nonce}"), Span::call_site());
-    quote! {
-        #[doc(hidden)]
-        #[macro_export]
-        macro_rules! #temporal_mac_name {
-            ($($t:tt)*) => {
-                #{&arg.newer_type}::__implement_internal! {
-                    /* Implementor */ ($($t)*)
-                    /* trait_def */ #input,
-                    /* alternative */ #{&arg.alternative},
-                    /* newer_type */ #crate_path,
-                    /* referrer */ #referrer,
-                    /* repeater */ #repeater_path,
-                    /* nonce */ #nonce
+    let registration = crate::emit_registration(
+        &input.vis,
+        &input.ident,
+        &temporal_mac_name,
+        quote! {
+            #{&arg.newer_type}::__implement_internal! {
+                /* Implementor */ ($($t)*)
+                /* protocol */ #{crate::protocol_header(arg.inline.is_none())}
+                /* trait_def */ #{replace_crate_keyword(quote!(#input))},
+                /* alternative */ #{replace_crate_keyword(quote!(#{&arg.alternative}))},
+                /* newer_type */ #crate_path,
+                /* referrer */ #{replace_crate_keyword(quote!(#referrer))},
+                /* repeater */ #repeater_path,
+                /* nonce */ #nonce,
+                #(if let Some(inline) = &arg.inline) {
+                    /* inline */ inline = #inline,
                 }
             }
-        }
-        #[doc(hidden)]
-        #{&input.vis} use #temporal_mac_name as #{&input.ident};
+        },
+    );
+    quote! {
+        #registration
         #[allow(private_bounds)]
         #[allow(clippy::missing_safety_doc)]
         #output
         #repeater_impl
+        #alternative_check
+        #complete_check
     }
 }
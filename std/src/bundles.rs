@@ -0,0 +1,98 @@
+//! Trait bundles implementing groups of the traits at once.
+//!
+//! - [`Arithmetic`] ... [`Add`](crate::ops::Add), [`Sub`](crate::ops::Sub),
+//!   [`Mul`](crate::ops::Mul), [`Div`](crate::ops::Div),
+//!   [`Rem`](crate::ops::Rem), the assigning variants and
+//!   [`Neg`](crate::ops::Neg).
+//! - [`Bitwise`] ... [`BitAnd`](crate::ops::BitAnd),
+//!   [`BitOr`](crate::ops::BitOr), [`BitXor`](crate::ops::BitXor),
+//!   [`Shl`](crate::ops::Shl), [`Shr`](crate::ops::Shr), the assigning
+//!   variants and [`Not`](crate::ops::Not).
+//! - [`Formatting`] ... [`Display`](crate::fmt::Display) and
+//!   [`Debug`](crate::fmt::Debug).
+//! - [`Comparison`] ... [`PartialEq`](crate::cmp::PartialEq),
+//!   [`Eq`](crate::cmp::Eq), [`PartialOrd`](crate::cmp::PartialOrd) and
+//!   [`Ord`](crate::cmp::Ord).
+//! - [`IteratorFull`] ... [`Iterator`](crate::iter::Iterator),
+//!   [`DoubleEndedIterator`](crate::iter::DoubleEndedIterator),
+//!   [`ExactSizeIterator`](crate::iter::ExactSizeIterator) and
+//!   [`FusedIterator`](crate::iter::FusedIterator).
+//! - [`IoStream`] ... `Read` and `Write` in `io` module (requires `std`
+//!   feature).
+//!
+//! The operator bundles take the right hand side type as the parameter, which
+//! defaults to `Self`.
+//!
+//! # Example
+//!
+//! ```
+//! use newer_type::implement;
+//! use newer_type_std::bundles::{Arithmetic, Comparison, Formatting};
+//!
+//! #[implement(Arithmetic, Comparison, Formatting)]
+//! #[derive(Clone, Copy)]
+//! struct Score(i32);
+//!
+//! let mut score = Score(3);
+//! score *= Score(2);
+//! assert_eq!(score + Score(4), 10);
+//! assert_eq!(-score, -6);
+//! assert!(score > Score(5));
+//! assert_eq!(format!("{score}"), "6");
+//! ```
+
+use newer_type::target_bundle;
+
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait Arithmetic<Rhs = Self>:
+    crate::ops::Add<Rhs>
+    + crate::ops::Sub<Rhs>
+    + crate::ops::Mul<Rhs>
+    + crate::ops::Div<Rhs>
+    + crate::ops::Rem<Rhs>
+    + crate::ops::AddAssign<Rhs>
+    + crate::ops::SubAssign<Rhs>
+    + crate::ops::MulAssign<Rhs>
+    + crate::ops::DivAssign<Rhs>
+    + crate::ops::RemAssign<Rhs>
+    + crate::ops::Neg
+{
+}
+
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait Bitwise<Rhs = Self>:
+    crate::ops::BitAnd<Rhs>
+    + crate::ops::BitOr<Rhs>
+    + crate::ops::BitXor<Rhs>
+    + crate::ops::Shl<Rhs>
+    + crate::ops::Shr<Rhs>
+    + crate::ops::BitAndAssign<Rhs>
+    + crate::ops::BitOrAssign<Rhs>
+    + crate::ops::BitXorAssign<Rhs>
+    + crate::ops::ShlAssign<Rhs>
+    + crate::ops::ShrAssign<Rhs>
+    + crate::ops::Not
+{
+}
+
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait Formatting: crate::fmt::Display + crate::fmt::Debug {}
+
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait Comparison:
+    crate::cmp::PartialEq + crate::cmp::Eq + crate::cmp::PartialOrd + crate::cmp::Ord
+{
+}
+
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait IteratorFull:
+    crate::iter::Iterator
+    + crate::iter::DoubleEndedIterator
+    + crate::iter::ExactSizeIterator
+    + crate::iter::FusedIterator
+{
+}
+
+#[cfg(feature = "std")]
+#[target_bundle(newer_type = crate::newer_type)]
+pub trait IoStream: crate::io::Read + crate::io::Write {}
@@ -4,7 +4,7 @@ use newer_type::target;
 emit_traits! {
     #[implement_of(newer_type_std::cmp::PartialEq)]
     #[slot(u8)]
-    #[target(alternative = ::core::cmp::PartialEq)]
+    #[target(alternative = ::core::cmp::PartialEq, complete)]
     pub trait PartialEq[Rhs = Self]
     where [Rhs: ?::core::marker::Sized,]
     {
@@ -12,17 +12,15 @@ emit_traits! {
         fn ne(&self, other: &Rhs) -> ::core::primitive::bool;
     }
 
-    #[implement_of(newer_type_std::cmp::PartialEq)]
-    #[implement_of(newer_type_std::cmp::Eq)]
+    #[implement_of(newer_type_std::cmp::Eq, with_supertraits)]
     #[slot(u8)]
     #[target(alternative = ::core::cmp::Eq)]
     pub trait Eq: [::core::cmp::PartialEq] {}
 
 
-    #[implement_of(newer_type_std::cmp::PartialEq)]
-    #[implement_of(newer_type_std::cmp::PartialOrd)]
+    #[implement_of(newer_type_std::cmp::PartialOrd, with_supertraits)]
     #[slot(u8)]
-    #[target(alternative = ::core::cmp::PartialOrd)]
+    #[target(alternative = ::core::cmp::PartialOrd, complete)]
     pub trait PartialOrd[Rhs = Self]: [::core::cmp::PartialEq<Rhs>]
     where [Rhs: ?::core::marker::Sized,]
     {
@@ -35,12 +33,9 @@ emit_traits! {
         fn ge(&self, other: &Rhs) -> ::core::primitive::bool;
     }
 
-    #[implement_of(newer_type_std::cmp::PartialEq)]
-    #[implement_of(newer_type_std::cmp::Eq)]
-    #[implement_of(newer_type_std::cmp::PartialOrd)]
-    #[implement_of(newer_type_std::cmp::Ord)]
+    #[implement_of(newer_type_std::cmp::Ord, with_supertraits)]
     #[slot(u8)]
-    #[target(alternative = ::core::cmp::Ord)]
+    #[target(alternative = ::core::cmp::Ord, complete)]
     pub trait Ord: [::core::cmp::Eq + ::core::cmp::PartialOrd] {
         fn cmp(&self, other: &Self) -> ::core::cmp::Ordering;
         // fn max(self, other: Self) -> Self
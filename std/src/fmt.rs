@@ -75,7 +75,7 @@ emit_traits! {
 
     #[implement_of(newer_type_std::fmt::Write)]
     #[slot(String)]
-    #[target(alternative = ::core::fmt::Write)]
+    #[target(alternative = ::core::fmt::Write, complete)]
     pub trait Write {
         fn write_str(&mut self, s: &::core::primitive::str) -> ::core::fmt::Result;
         fn write_char(&mut self, c: ::core::primitive::char) -> ::core::fmt::Result;
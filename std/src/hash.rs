@@ -32,7 +32,7 @@ emit_traits! {
 
     #[implement_of(newer_type_std::hash::BuildHasher)]
     #[slot(std::hash::RandomState)]
-    #[target(alternative = ::core::hash::BuildHasher)]
+    #[target(alternative = ::core::hash::BuildHasher, complete)]
     pub trait BuildHasher {
         type Hasher: ::core::hash::Hasher;
         fn build_hasher(&self) -> Self::Hasher;
@@ -4,18 +4,48 @@ use newer_type::target;
 emit_traits! {
     #[implement_of(newer_type_std::io::Read)]
     #[slot(std::io::Cursor<Vec<u8>>)]
-    #[target(alternative = ::std::io::Read)]
+    #[target(alternative = ::std::io::Read, complete)]
     pub trait Read {
         fn read(&mut self, buf: &mut [::core::primitive::u8]) -> ::std::io::Result<::core::primitive::usize>;
+        fn read_vectored(
+            &mut self,
+            bufs: &mut [::std::io::IoSliceMut<'_>],
+        ) -> ::std::io::Result<::core::primitive::usize>;
+        fn read_to_end(
+            &mut self,
+            buf: &mut ::std::vec::Vec<::core::primitive::u8>,
+        ) -> ::std::io::Result<::core::primitive::usize>;
+        fn read_to_string(
+            &mut self,
+            buf: &mut ::std::string::String,
+        ) -> ::std::io::Result<::core::primitive::usize>;
+        fn read_exact(&mut self, buf: &mut [::core::primitive::u8]) -> ::std::io::Result<()>;
+    }
+    nightly {
+        fn is_read_vectored(&self) -> ::core::primitive::bool;
+        fn read_buf(&mut self, buf: ::std::io::BorrowedCursor<'_>) -> ::std::io::Result<()>;
+        fn read_buf_exact(&mut self, cursor: ::std::io::BorrowedCursor<'_>) -> ::std::io::Result<()>;
     }
 
-    #[implement_of(newer_type_std::io::Read)]
+    #[implement_of(newer_type_std::io::Write)]
     #[slot(std::io::Cursor<Vec<u8>>)]
-    #[target(alternative = ::std::io::Write)]
+    #[target(alternative = ::std::io::Write, complete)]
     pub trait Write {
         fn write(&mut self, buf: &[::core::primitive::u8]) -> ::std::io::Result<::core::primitive::usize>;
+        fn write_vectored(
+            &mut self,
+            bufs: &[::std::io::IoSlice<'_>],
+        ) -> ::std::io::Result<::core::primitive::usize>;
         fn flush(&mut self) -> ::std::io::Result<()>;
         fn write_all(&mut self, mut buf: &[::core::primitive::u8]) -> ::std::io::Result<()>;
+        fn write_fmt(&mut self, fmt: ::core::fmt::Arguments<'_>) -> ::std::io::Result<()>;
+    }
+    nightly {
+        fn is_write_vectored(&self) -> ::core::primitive::bool;
+        fn write_all_vectored(
+            &mut self,
+            bufs: &mut [::std::io::IoSlice<'_>],
+        ) -> ::std::io::Result<()>;
     }
 
     #[implement_of(
@@ -23,19 +53,34 @@ emit_traits! {
         newer_type_std::io::BufRead,
     )]
     #[slot(std::io::Cursor<Vec<u8>>)]
-    #[target(alternative = ::std::io::BufRead)]
+    #[target(alternative = ::std::io::BufRead, complete)]
     pub trait BufRead: [::std::io::Read] {
         fn fill_buf(&mut self) -> ::std::io::Result<&[::core::primitive::u8]>;
         fn consume(&mut self, amt: ::core::primitive::usize);
+        fn read_until(
+            &mut self,
+            byte: ::core::primitive::u8,
+            buf: &mut ::std::vec::Vec<::core::primitive::u8>,
+        ) -> ::std::io::Result<::core::primitive::usize>;
+        #[rustversion::since(1.83)]
+        fn skip_until(&mut self, byte: ::core::primitive::u8) -> ::std::io::Result<::core::primitive::usize>;
+        fn read_line(&mut self, buf: &mut ::std::string::String) -> ::std::io::Result<::core::primitive::usize>;
+    }
+    nightly {
+        fn has_data_left(&mut self) -> ::std::io::Result<::core::primitive::bool>;
     }
 
     #[implement_of(newer_type_std::io::Seek)]
     #[slot(std::fs::File)]
-    #[target(alternative = ::std::io::Seek)]
+    #[target(alternative = ::std::io::Seek, complete)]
     pub trait Seek {
         fn seek(&mut self, pos: ::std::io::SeekFrom) -> ::std::io::Result<::core::primitive::u64>;
         fn rewind(&mut self) -> ::std::io::Result<()>;
         fn stream_position(&mut self) -> ::std::io::Result<::core::primitive::u64>;
+        #[rustversion::since(1.80)]
         fn seek_relative(&mut self, offset: ::core::primitive::i64) -> ::std::io::Result<()>;
     }
+    nightly {
+        fn stream_len(&mut self) -> ::std::io::Result<::core::primitive::u64>;
+    }
 }
@@ -22,7 +22,7 @@ emit_traits! {
 
     #[implement_of(newer_type_std::iter::Iterator)]
     #[slot(std::vec::IntoIter<u8>)]
-    #[target(alternative = ::core::iter::Iterator)]
+    #[target(alternative = ::core::iter::Iterator, complete)]
     pub trait Iterator {
         type Item;
         fn next(&mut self) -> ::core::option::Option<Self::Item>;
@@ -39,25 +39,220 @@ emit_traits! {
         where
             Self: ::core::marker::Sized;
         fn nth(&mut self, n: ::core::primitive::usize) -> ::core::option::Option<Self::Item>;
+        fn for_each<F>(self, f: F)
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item);
+        fn collect<B>(self) -> B
+        where
+            B: ::core::iter::FromIterator<Self::Item>,
+            Self: ::core::marker::Sized;
+        fn partition<B, F>(self, f: F) -> (B, B)
+        where
+            Self: ::core::marker::Sized,
+            B: ::core::default::Default + ::core::iter::Extend<Self::Item>,
+            F: ::core::ops::FnMut(&Self::Item) -> ::core::primitive::bool;
+        fn fold<B, F>(self, init: B, f: F) -> B
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(B, Self::Item) -> B;
+        fn reduce<F>(self, f: F) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item, Self::Item) -> Self::Item;
+        fn all<F>(&mut self, f: F) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item) -> ::core::primitive::bool;
+        fn any<F>(&mut self, f: F) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item) -> ::core::primitive::bool;
+        fn find<P>(&mut self, predicate: P) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            P: ::core::ops::FnMut(&Self::Item) -> ::core::primitive::bool;
+        fn find_map<B, F>(&mut self, f: F) -> ::core::option::Option<B>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item) -> ::core::option::Option<B>;
+        fn position<P>(&mut self, predicate: P) -> ::core::option::Option<::core::primitive::usize>
+        where
+            Self: ::core::marker::Sized,
+            P: ::core::ops::FnMut(Self::Item) -> ::core::primitive::bool;
+        fn max(self) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            Self::Item: ::core::cmp::Ord;
+        fn min(self) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            Self::Item: ::core::cmp::Ord;
+        fn max_by_key<B: ::core::cmp::Ord, F>(self, f: F) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(&Self::Item) -> B;
+        fn max_by<F>(self, compare: F) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(&Self::Item, &Self::Item) -> ::core::cmp::Ordering;
+        fn min_by_key<B: ::core::cmp::Ord, F>(self, f: F) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(&Self::Item) -> B;
+        fn min_by<F>(self, compare: F) -> ::core::option::Option<Self::Item>
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(&Self::Item, &Self::Item) -> ::core::cmp::Ordering;
+        fn sum<S>(self) -> S
+        where
+            Self: ::core::marker::Sized,
+            S: ::core::iter::Sum<Self::Item>;
+        fn product<P>(self) -> P
+        where
+            Self: ::core::marker::Sized,
+            P: ::core::iter::Product<Self::Item>;
+        fn cmp<I>(self, other: I) -> ::core::cmp::Ordering
+        where
+            I: ::core::iter::IntoIterator<Item = Self::Item>,
+            Self::Item: ::core::cmp::Ord,
+            Self: ::core::marker::Sized;
+        fn partial_cmp<I>(self, other: I) -> ::core::option::Option<::core::cmp::Ordering>
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialOrd<I::Item>,
+            Self: ::core::marker::Sized;
+        fn eq<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialEq<I::Item>,
+            Self: ::core::marker::Sized;
+        fn ne<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialEq<I::Item>,
+            Self: ::core::marker::Sized;
+        fn lt<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialOrd<I::Item>,
+            Self: ::core::marker::Sized;
+        fn le<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialOrd<I::Item>,
+            Self: ::core::marker::Sized;
+        fn gt<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialOrd<I::Item>,
+            Self: ::core::marker::Sized;
+        fn ge<I>(self, other: I) -> ::core::primitive::bool
+        where
+            I: ::core::iter::IntoIterator,
+            Self::Item: ::core::cmp::PartialOrd<I::Item>,
+            Self: ::core::marker::Sized;
+        #[rustversion::since(1.82)]
+        fn is_sorted(self) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            Self::Item: ::core::cmp::PartialOrd;
+        #[rustversion::since(1.82)]
+        fn is_sorted_by<F>(self, compare: F) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(&Self::Item, &Self::Item) -> ::core::primitive::bool;
+        #[rustversion::since(1.82)]
+        fn is_sorted_by_key<F, K>(self, f: F) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item) -> K,
+            K: ::core::cmp::PartialOrd;
+    }
+    nightly {
+        fn next_chunk<const N: ::core::primitive::usize>(
+            &mut self,
+        ) -> ::core::result::Result<[Self::Item; N], ::core::array::IntoIter<Self::Item, N>>
+        where
+            Self: ::core::marker::Sized;
+        fn advance_by(
+            &mut self,
+            n: ::core::primitive::usize,
+        ) -> ::core::result::Result<(), ::core::num::NonZero<::core::primitive::usize>>;
+        fn collect_into<E: ::core::iter::Extend<Self::Item>>(self, collection: &mut E) -> &mut E
+        where
+            Self: ::core::marker::Sized;
+        fn is_partitioned<P>(self, predicate: P) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            P: ::core::ops::FnMut(Self::Item) -> ::core::primitive::bool;
+        fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(B, Self::Item) -> R,
+            R: ::core::ops::Try<Output = B>;
+        fn try_for_each<F, R>(&mut self, f: F) -> R
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(Self::Item) -> R,
+            R: ::core::ops::Try<Output = ()>;
+        fn try_reduce<R>(
+            &mut self,
+            f: impl ::core::ops::FnMut(Self::Item, Self::Item) -> R,
+        ) -> <R::Residual as ::core::ops::Residual<::core::option::Option<R::Output>>>::TryType
+        where
+            Self: ::core::marker::Sized,
+            R: ::core::ops::Try<Output = Self::Item>,
+            R::Residual: ::core::ops::Residual<::core::option::Option<Self::Item>>;
+        fn try_find<R>(
+            &mut self,
+            f: impl ::core::ops::FnMut(&Self::Item) -> R,
+        ) -> <R::Residual as ::core::ops::Residual<::core::option::Option<Self::Item>>>::TryType
+        where
+            Self: ::core::marker::Sized,
+            R: ::core::ops::Try<Output = ::core::primitive::bool>,
+            R::Residual: ::core::ops::Residual<::core::option::Option<Self::Item>>;
+        fn cmp_by<I, F>(self, other: I, cmp: F) -> ::core::cmp::Ordering
+        where
+            Self: ::core::marker::Sized,
+            I: ::core::iter::IntoIterator,
+            F: ::core::ops::FnMut(Self::Item, I::Item) -> ::core::cmp::Ordering;
+        fn partial_cmp_by<I, F>(
+            self,
+            other: I,
+            partial_cmp: F,
+        ) -> ::core::option::Option<::core::cmp::Ordering>
+        where
+            Self: ::core::marker::Sized,
+            I: ::core::iter::IntoIterator,
+            F: ::core::ops::FnMut(Self::Item, I::Item) -> ::core::option::Option<::core::cmp::Ordering>;
+        fn eq_by<I, F>(self, other: I, eq: F) -> ::core::primitive::bool
+        where
+            Self: ::core::marker::Sized,
+            I: ::core::iter::IntoIterator,
+            F: ::core::ops::FnMut(Self::Item, I::Item) -> ::core::primitive::bool;
     }
 
     #[implement_of(newer_type_std::iter::Iterator, newer_type_std::iter::FusedIterator)]
     #[slot(std::vec::IntoIter<u8>)]
-    #[target(alternative = ::core::iter::FusedIterator)]
+    #[target(alternative = ::core::iter::FusedIterator, complete)]
     pub trait FusedIterator: [::core::iter::Iterator] {}
 
 
     #[implement_of(newer_type_std::iter::Iterator, newer_type_std::iter::ExactSizeIterator)]
     #[slot(std::vec::IntoIter<u8>)]
-    #[target(alternative = ::core::iter::ExactSizeIterator)]
+    #[target(alternative = ::core::iter::ExactSizeIterator, complete)]
     pub trait ExactSizeIterator: [::core::iter::Iterator] {
         fn len(&self) -> ::core::primitive::usize;
     }
+    nightly {
+        fn is_empty(&self) -> ::core::primitive::bool;
+    }
 
 
     #[implement_of(newer_type_std::iter::Iterator, newer_type_std::iter::DoubleEndedIterator)]
     #[slot(std::vec::IntoIter<u8>)]
-    #[target(alternative = ::core::iter::DoubleEndedIterator)]
+    #[target(alternative = ::core::iter::DoubleEndedIterator, complete)]
     pub trait DoubleEndedIterator: [::core::iter::Iterator] {
         fn next_back(&mut self) -> ::core::option::Option<Self::Item>;
 
@@ -65,11 +260,6 @@ emit_traits! {
             &mut self,
             n: ::core::primitive::usize,
         ) -> ::core::option::Option<Self::Item>;
-        // fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
-        // where
-        //     Self: ::core::marker::Sized,
-        //     F: ::core::ops::FnMut(B, Self::Item) -> R,
-        //     R: ::core::ops::Try<Output = B>;
         fn rfold<B, F>(self, init: B, f: F) -> B
         where
             Self: ::core::marker::Sized,
@@ -79,4 +269,15 @@ emit_traits! {
             Self: ::core::marker::Sized,
             P: ::core::ops::FnMut(&Self::Item) -> ::core::primitive::bool;
     }
+    nightly {
+        fn advance_back_by(
+            &mut self,
+            n: ::core::primitive::usize,
+        ) -> ::core::result::Result<(), ::core::num::NonZero<::core::primitive::usize>>;
+        fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
+        where
+            Self: ::core::marker::Sized,
+            F: ::core::ops::FnMut(B, Self::Item) -> R,
+            R: ::core::ops::Try<Output = B>;
+    }
 }
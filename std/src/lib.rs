@@ -1,7 +1,41 @@
+//! Target traits for the traits in `core` and `std`, to be used with
+//! [`newer_type::implement`].
+//!
+//! # Features
+//!
+//! - `std` (default) ... The traits in `std`, like `io::Read`.
+//! - `nightly` ... Adds `nightly` modules, like `iter::nightly`, with the
+//!   traits forwarding also the unstable methods, like `Iterator::try_fold`.
+//!   Requires the nightly compiler. The crates implementing the traits in
+//!   `nightly` modules should enable the corresponding unstable features with
+//!   `#![feature(...)]`, while the other traits are the same as without the
+//!   feature.
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        buf_read_has_data_left,
+        can_vector,
+        core_io_borrowed_buf,
+        exact_size_is_empty,
+        iter_advance_by,
+        iter_collect_into,
+        iter_is_partitioned,
+        iter_next_chunk,
+        iter_order_by,
+        iterator_try_reduce,
+        read_buf,
+        seek_stream_len,
+        try_find,
+        try_trait_v2,
+        try_trait_v2_residual,
+        write_all_vectored
+    )
+)]
 
 pub mod alloc;
 pub mod borrow;
+pub mod bundles;
 pub mod cmp;
 pub mod convert;
 pub mod error;
@@ -24,23 +58,119 @@ pub mod task;
 pub use newer_type;
 
 macro_rules! emit_traits {
-    () => {};
+    (@nightly []) => {};
+    // The traits also with the items in `nightly { ... }`, collected while
+    // emitting the others.
+    (@nightly [$($nightly:tt)+]) => {
+        /// The traits forwarding also the unstable methods, available with
+        /// `nightly` feature.
+        ///
+        /// The crates implementing them should enable the corresponding
+        /// unstable features with `#![feature(...)]`.
+        #[cfg(feature = "nightly")]
+        pub mod nightly {
+            use crate::emit_traits;
+            use newer_type::target;
+
+            emit_traits! { $($nightly)+ }
+        }
+    };
+    // The items in `nightly { ... }` are declared only in the trait in
+    // `nightly` module, so that the crates implementing the trait without them
+    // need not to enable the unstable features.
+    (
+        @nightly [$($nightly:tt)*]
+        $(#[doc = $doc0:literal])*
+        $(
+            $(#[implement_of($($implement_of:tt)*)])+
+            #[slot($($slot_ty:tt)*)]
+        )?
+        $(#[doc = $doc1:literal])*
+        #[target(alternative = $alternative:path $(, $target_flag:ident)*)]
+        $(#[$($other_attr:tt)*])*
+        pub trait $trait_name:ident $([$($trait_params:tt)+])? $(: [$($supertraits:tt)*])?
+        $(where [$($where_clause:tt)*])?
+        {$($trait_contents:tt)*}
+        nightly {$($nightly_contents:tt)*}
+        $($t:tt)*
+    ) => {
+        emit_traits! {
+            @trait
+            $(#[doc = $doc0])*
+            $(
+                $(#[implement_of($($implement_of)*)])+
+                #[slot($($slot_ty)*)]
+            )?
+            $(#[doc = $doc1])*
+            #[target(alternative = $alternative $(, $target_flag)*)]
+            $(#[$($other_attr)*])*
+            pub trait $trait_name $([$($trait_params)+])? $(: [$($supertraits)*])?
+            $(where [$($where_clause)*])?
+            {$($trait_contents)*}
+        }
+        emit_traits! {
+            @nightly [
+                $($nightly)*
+                #[target(alternative = $alternative $(, $target_flag)*)]
+                #[doc = "Forwards also the unstable methods."]
+                #[doc = ""]
+                $(#[doc = $doc0])*
+                $(#[doc = $doc1])*
+                $(#[$($other_attr)*])*
+                pub trait $trait_name $([$($trait_params)+])? $(: [$($supertraits)*])?
+                $(where [$($where_clause)*])?
+                {$($trait_contents)* $($nightly_contents)*}
+            ]
+            $($t)*
+        }
+    };
     (
+        @nightly [$($nightly:tt)*]
         $(#[doc = $doc0:literal])*
         $(
             $(#[implement_of($($implement_of:tt)*)])+
             #[slot($($slot_ty:tt)*)]
         )?
         $(#[doc = $doc1:literal])*
-        #[target(alternative = $alternative:path)]
+        #[target(alternative = $alternative:path $(, $target_flag:ident)*)]
         $(#[$($other_attr:tt)*])*
         pub trait $trait_name:ident $([$($trait_params:tt)+])? $(: [$($supertraits:tt)*])?
         $(where [$($where_clause:tt)*])?
         {$($trait_contents:tt)*}
         $($t:tt)*
+    ) => {
+        emit_traits! {
+            @trait
+            $(#[doc = $doc0])*
+            $(
+                $(#[implement_of($($implement_of)*)])+
+                #[slot($($slot_ty)*)]
+            )?
+            $(#[doc = $doc1])*
+            #[target(alternative = $alternative $(, $target_flag)*)]
+            $(#[$($other_attr)*])*
+            pub trait $trait_name $([$($trait_params)+])? $(: [$($supertraits)*])?
+            $(where [$($where_clause)*])?
+            {$($trait_contents)*}
+        }
+        emit_traits! { @nightly [$($nightly)*] $($t)* }
+    };
+    (
+        @trait
+        $(#[doc = $doc0:literal])*
+        $(
+            $(#[implement_of($($implement_of:tt)*)])+
+            #[slot($($slot_ty:tt)*)]
+        )?
+        $(#[doc = $doc1:literal])*
+        #[target(alternative = $alternative:path $(, $target_flag:ident)*)]
+        $(#[$($other_attr:tt)*])*
+        pub trait $trait_name:ident $([$($trait_params:tt)+])? $(: [$($supertraits:tt)*])?
+        $(where [$($where_clause:tt)*])?
+        {$($trait_contents:tt)*}
     ) => {
         $(#[$($other_attr)*])*
-        #[target(alternative = $alternative, newer_type = $crate::newer_type, repeater = $crate::Repeater)]
+        #[target(alternative = $alternative, $($target_flag,)* newer_type = $crate::newer_type, repeater = $crate::Repeater)]
         $(
             #[doc = $doc0]
             #[doc = ""]
@@ -66,7 +196,9 @@ macro_rules! emit_traits {
         pub trait $trait_name $(< $($trait_params)+>)? $(:$($supertraits)*)?
         $(where $($where_clause)*)?
         {$($trait_contents)*}
-        emit_traits!{ $($t)* }
+    };
+    ($($t:tt)*) => {
+        emit_traits! { @nightly [] $($t)* }
     };
 }
 use emit_traits;
@@ -0,0 +1,77 @@
+use newer_type::implement;
+use newer_type_std::bundles::{
+    Arithmetic, Bitwise, Comparison, Formatting, IoStream, IteratorFull,
+};
+use std::io::{Cursor, Read, Write};
+
+#[implement(Arithmetic, Bitwise, Comparison, Formatting)]
+#[derive(Clone, Copy)]
+pub struct Flags(i16);
+
+#[test]
+fn test_arithmetic_bitwise() {
+    let mut flags = Flags(0b0101);
+    assert_eq!(flags + Flags(1), 6);
+    assert_eq!(flags % Flags(2), 1);
+    flags -= Flags(1);
+    assert_eq!(flags | Flags(0b0010), 0b0110);
+    assert_eq!(flags << Flags(1), 0b1000);
+    assert_eq!(!flags, !0b0100);
+    assert_eq!(-flags, -0b0100);
+    flags ^= Flags(0b1100);
+    assert_eq!(flags.0, 0b1000);
+}
+
+#[test]
+fn test_comparison_formatting() {
+    assert!(Flags(1) == Flags(1));
+    assert!(Flags(1) < Flags(2));
+    assert_eq!(Flags(3).max(Flags(2)).0, 3);
+    assert_eq!(format!("{} {:?}", Flags(4), Flags(5)), "4 5");
+}
+
+#[implement(for<Rhs> Arithmetic<Rhs>)]
+pub struct Meters(f64);
+
+#[test]
+fn test_generic_bundle() {
+    let mut m = Meters(1.5);
+    m *= 2.0;
+    assert_eq!(m.0, 3.0);
+    assert_eq!(m - 1.0, 2.0);
+    assert_eq!(-Meters(0.5), -0.5);
+}
+
+#[implement(IteratorFull)]
+pub struct Countdown(std::iter::Rev<std::ops::Range<u32>>);
+
+#[test]
+fn test_iterator_full() {
+    let mut it = Countdown((0..4).rev());
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next(), Some(3));
+    assert_eq!(it.next_back(), Some(0));
+    assert_eq!(it.collect::<Vec<_>>(), vec![2, 1]);
+}
+
+#[implement(IoStream)]
+pub struct Stream(Cursor<Vec<u8>>);
+
+#[test]
+fn test_io_stream() {
+    let mut stream = Stream(Cursor::new(Vec::new()));
+    stream.write_all(b"hello").unwrap();
+    stream.0.set_position(0);
+    let mut buf = String::new();
+    stream.read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "hello");
+}
+
+#[implement(newer_type_std::bundles::Comparison, with_supertraits)]
+pub struct Level(i32);
+
+#[test]
+fn test_bundle_with_supertraits() {
+    assert!(Level(1) == Level(1));
+    assert!(Level(-1) < Level(1));
+}
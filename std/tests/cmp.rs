@@ -52,3 +52,29 @@ fn test_ord() {
     let b = MyString::new("banana");
     assert!(a < b);
 }
+
+#[implement(newer_type_std::cmp::Ord, with_supertraits)]
+#[derive(Debug)]
+pub struct Version(u32);
+
+#[implement(newer_type_std::cmp::PartialEq)]
+#[implement(newer_type_std::cmp::Ord, with_supertraits)]
+#[derive(Debug)]
+pub struct Revision {
+    version: Version,
+    _label: &'static str,
+}
+
+#[test]
+fn test_with_supertraits() {
+    assert_eq!(Version(1), Version(1));
+    assert!(Version(1) < Version(2));
+    assert_eq!(Version(3).cmp(&Version(2)), std::cmp::Ordering::Greater);
+    let revision = |n, label| Revision {
+        version: Version(n),
+        _label: label,
+    };
+    assert_eq!(revision(1, "a"), revision(1, "b"));
+    assert!(revision(1, "b") < revision(2, "a"));
+    assert_eq!(revision(1, "a").max(revision(3, "a")).version, Version(3));
+}
@@ -91,3 +91,20 @@ fn test_generic_error_enum() {
     assert!(format!("{:?}", e2).contains("file not found"));
     assert!(e1.source().is_none());
 }
+
+// `Debug` and `Display` are not declared alongside `Error`, so they are not
+// delegated by `with_supertraits`.
+#[implement(newer_type_std::fmt::Display, newer_type_std::fmt::Debug)]
+#[implement(newer_type_std::error::Error, with_supertraits)]
+pub struct MyWalkedError {
+    inner: io::Error,
+}
+
+#[test]
+fn test_error_with_supertraits() {
+    let err = MyWalkedError {
+        inner: io::Error::new(io::ErrorKind::Other, "walked"),
+    };
+    assert_eq!(err.to_string(), "walked");
+    assert!(err.source().is_none());
+}
@@ -1,5 +1,7 @@
 use newer_type::implement;
-use std::io::{BufRead, Cursor, Read, Seek, SeekFrom, Write};
+use std::cell::RefCell;
+use std::io::{BufRead, Cursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use std::rc::Rc;
 
 #[implement(
     newer_type_std::io::Read,
@@ -67,3 +69,140 @@ fn test_io_enum_read_write_seek() {
     wrapper.read_to_end(&mut buf2).unwrap();
     assert_eq!(&buf2, b"xyz");
 }
+
+pub struct Probe {
+    inner: Cursor<Vec<u8>>,
+    calls: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Probe {
+    fn new(data: &[u8]) -> (Self, Rc<RefCell<Vec<&'static str>>>) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let probe = Probe {
+            inner: Cursor::new(data.to_vec()),
+            calls: calls.clone(),
+        };
+        (probe, calls)
+    }
+
+    fn record(&self, name: &'static str) {
+        self.calls.borrow_mut().push(name);
+    }
+}
+
+impl Read for Probe {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> std::io::Result<usize> {
+        self.record("read_vectored");
+        self.inner.read_vectored(bufs)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> std::io::Result<usize> {
+        self.record("read_to_end");
+        self.inner.read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        self.record("read_to_string");
+        self.inner.read_to_string(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.record("read_exact");
+        self.inner.read_exact(buf)
+    }
+}
+
+impl Write for Probe {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        self.record("write_vectored");
+        self.inner.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.record("write_all");
+        self.inner.write_all(buf)
+    }
+
+    fn write_fmt(&mut self, fmt: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+        self.record("write_fmt");
+        self.inner.write_fmt(fmt)
+    }
+}
+
+impl BufRead for Probe {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> std::io::Result<usize> {
+        self.record("read_until");
+        self.inner.read_until(byte, buf)
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        self.record("read_line");
+        self.inner.read_line(buf)
+    }
+}
+
+#[implement(
+    newer_type_std::io::Read,
+    newer_type_std::io::Write,
+    newer_type_std::io::BufRead
+)]
+pub struct Probed(Probe);
+
+#[test]
+fn test_io_overrides_reached() {
+    let (probe, calls) = Probe::new(b"ab\ncd\nef");
+    let mut probed = Probed(probe);
+    let mut line = String::new();
+    probed.read_line(&mut line).unwrap();
+    assert_eq!(line, "ab\n");
+    let mut until = Vec::new();
+    probed.read_until(b'\n', &mut until).unwrap();
+    assert_eq!(until, b"cd\n");
+    let (mut first, mut second) = ([0u8; 1], [0u8; 1]);
+    let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+    assert_eq!(probed.read_vectored(&mut bufs).unwrap(), 2);
+    assert_eq!((first, second), ([b'e'], [b'f']));
+    let mut rest = Vec::new();
+    probed.read_to_end(&mut rest).unwrap();
+    probed.read_to_string(&mut line).unwrap();
+    assert!(probed.read_exact(&mut [0u8; 1]).is_err());
+    probed.write_all(b"gh").unwrap();
+    let bufs = [IoSlice::new(b"i"), IoSlice::new(b"j")];
+    assert_eq!(probed.write_vectored(&bufs).unwrap(), 2);
+    write!(probed, "{}", 1).unwrap();
+    assert_eq!(probed.0.inner.get_ref(), b"ab\ncd\nefghij1");
+    assert_eq!(
+        *calls.borrow(),
+        [
+            "read_line",
+            "read_until",
+            "read_vectored",
+            "read_to_end",
+            "read_to_string",
+            "read_exact",
+            "write_all",
+            "write_vectored",
+            "write_fmt"
+        ]
+    );
+}
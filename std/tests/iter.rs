@@ -1,6 +1,8 @@
 use newer_type::implement;
+use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::iter::{ExactSizeIterator, Extend, Iterator};
+use std::rc::Rc;
 
 #[implement(newer_type_std::iter::IntoIterator)]
 #[implement(for<A> newer_type_std::iter::Extend<A>)]
@@ -98,3 +100,213 @@ fn test_enum_variant_b_iterator_behavior() {
     assert_eq!(wrapper.next(), Some('b'));
     assert_eq!(wrapper.next(), None);
 }
+
+#[implement(
+    newer_type_std::iter::IntoIterator,
+    ref newer_type_std::iter::IntoIterator,
+    mut newer_type_std::iter::IntoIterator
+)]
+pub struct MyVec<T>(Vec<T>);
+
+#[test]
+fn test_into_iterator_for_refs() {
+    let mut my_vec = MyVec(vec![1, 2, 3]);
+    for x in &mut my_vec {
+        *x *= 2;
+    }
+    let borrowed: Vec<&i32> = (&my_vec).into_iter().collect();
+    assert_eq!(borrowed, vec![&2, &4, &6]);
+    let mut sum = 0;
+    for x in &my_vec {
+        sum += x;
+    }
+    assert_eq!(sum, 12);
+    assert_eq!(my_vec.into_iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+}
+
+/// Iterator recording the methods called, to see that the overrides are
+/// reached through the wrapper.
+pub struct Probe {
+    inner: std::vec::IntoIter<u32>,
+    calls: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Probe {
+    fn new(items: Vec<u32>) -> (Self, Rc<RefCell<Vec<&'static str>>>) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let probe = Probe {
+            inner: items.into_iter(),
+            calls: calls.clone(),
+        };
+        (probe, calls)
+    }
+
+    fn record(&self, name: &'static str) {
+        self.calls.borrow_mut().push(name);
+    }
+}
+
+impl Iterator for Probe {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.record("size_hint");
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.record("count");
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u32> {
+        self.record("nth");
+        self.inner.nth(n)
+    }
+
+    fn for_each<F: FnMut(u32)>(self, f: F) {
+        self.record("for_each");
+        self.inner.for_each(f)
+    }
+
+    fn fold<B, F: FnMut(B, u32) -> B>(self, init: B, f: F) -> B {
+        self.record("fold");
+        self.inner.fold(init, f)
+    }
+
+    fn all<F: FnMut(u32) -> bool>(&mut self, f: F) -> bool {
+        self.record("all");
+        self.inner.all(f)
+    }
+
+    fn any<F: FnMut(u32) -> bool>(&mut self, f: F) -> bool {
+        self.record("any");
+        self.inner.any(f)
+    }
+
+    fn position<P: FnMut(u32) -> bool>(&mut self, predicate: P) -> Option<usize> {
+        self.record("position");
+        self.inner.position(predicate)
+    }
+
+    fn max(self) -> Option<u32> {
+        self.record("max");
+        self.inner.max()
+    }
+
+    fn sum<S: std::iter::Sum<u32>>(self) -> S {
+        self.record("sum");
+        self.inner.sum()
+    }
+
+    fn partial_cmp<I>(self, other: I) -> Option<std::cmp::Ordering>
+    where
+        I: IntoIterator,
+        u32: PartialOrd<I::Item>,
+    {
+        self.record("partial_cmp");
+        self.inner.partial_cmp(other)
+    }
+}
+
+impl DoubleEndedIterator for Probe {
+    fn next_back(&mut self) -> Option<u32> {
+        self.inner.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<u32> {
+        self.record("nth_back");
+        self.inner.nth_back(n)
+    }
+
+    fn rfold<B, F: FnMut(B, u32) -> B>(self, init: B, f: F) -> B {
+        self.record("rfold");
+        self.inner.rfold(init, f)
+    }
+}
+
+impl ExactSizeIterator for Probe {
+    fn len(&self) -> usize {
+        self.record("len");
+        self.inner.len()
+    }
+}
+
+#[implement(
+    newer_type_std::iter::Iterator,
+    newer_type_std::iter::ExactSizeIterator,
+    newer_type_std::iter::DoubleEndedIterator
+)]
+pub struct Probed(Probe);
+
+#[implement(
+    newer_type_std::iter::Iterator,
+    newer_type_std::iter::ExactSizeIterator,
+    newer_type_std::iter::DoubleEndedIterator
+)]
+pub enum EitherProbe {
+    Probe(Probe),
+    Probed(Probed),
+}
+
+type Consumer = (&'static str, fn(Probed));
+
+#[test]
+fn test_iterator_overrides_reached() {
+    let (probe, calls) = Probe::new(vec![1, 2, 3, 4]);
+    let mut probed = Probed(probe);
+    assert_eq!(probed.size_hint(), (4, Some(4)));
+    assert_eq!(probed.len(), 4);
+    assert_eq!(probed.nth(1), Some(2));
+    assert_eq!(probed.nth_back(0), Some(4));
+    assert!(!probed.any(|n| n == 2));
+    assert!(probed.all(|n| n == 3));
+    assert_eq!(probed.position(|_| true), None);
+    assert_eq!(
+        *calls.borrow(),
+        [
+            "size_hint",
+            "len",
+            "nth",
+            "nth_back",
+            "any",
+            "all",
+            "position"
+        ]
+    );
+
+    let consumers: [Consumer; 7] = [
+        ("count", |p| assert_eq!(p.count(), 3)),
+        ("fold", |p| assert_eq!(p.fold(0, |a, n| a * 10 + n), 123)),
+        ("rfold", |p| assert_eq!(p.rfold(0, |a, n| a * 10 + n), 321)),
+        ("for_each", |p| p.for_each(|n| assert!(n > 0))),
+        ("max", |p| assert_eq!(p.max(), Some(3))),
+        ("sum", |p| assert_eq!(p.sum::<u32>(), 6)),
+        ("partial_cmp", |p| {
+            assert_eq!(p.partial_cmp([1, 2]), Some(std::cmp::Ordering::Greater))
+        }),
+    ];
+    for (name, consume) in consumers {
+        let (probe, calls) = Probe::new(vec![1, 2, 3]);
+        consume(Probed(probe));
+        assert_eq!(*calls.borrow(), [name]);
+    }
+}
+
+#[test]
+fn test_iterator_overrides_reached_for_enum() {
+    let (probe, calls) = Probe::new(vec![1, 2, 3]);
+    let either = EitherProbe::Probed(Probed(probe));
+    assert_eq!(either.fold(0, |a, n| a * 10 + n), 123);
+    assert_eq!(*calls.borrow(), ["fold"]);
+
+    let (probe, calls) = Probe::new(vec![1, 2, 3]);
+    let mut either = EitherProbe::Probe(probe);
+    assert_eq!(either.nth_back(1), Some(2));
+    assert_eq!(either.len(), 1);
+    assert_eq!(*calls.borrow(), ["nth_back", "len"]);
+}
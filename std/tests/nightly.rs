@@ -0,0 +1,129 @@
+#![cfg(feature = "nightly")]
+// The features used by the methods of the implemented traits
+#![feature(
+    buf_read_has_data_left,
+    can_vector,
+    core_io_borrowed_buf,
+    exact_size_is_empty,
+    iter_advance_by,
+    iter_collect_into,
+    iter_is_partitioned,
+    iter_next_chunk,
+    iter_order_by,
+    iterator_try_reduce,
+    read_buf,
+    try_find,
+    try_trait_v2,
+    try_trait_v2_residual
+)]
+use newer_type::implement;
+use std::cell::RefCell;
+use std::io::{BufRead, Cursor, Read};
+use std::iter::{ExactSizeIterator, Iterator};
+use std::rc::Rc;
+
+/// Records the unstable methods called, to see that the overrides are reached
+/// through the wrapper.
+pub struct Probe<T> {
+    inner: T,
+    calls: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl<T> Probe<T> {
+    fn new(inner: T) -> (Self, Rc<RefCell<Vec<&'static str>>>) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let probe = Probe {
+            inner,
+            calls: calls.clone(),
+        };
+        (probe, calls)
+    }
+
+    fn record(&self, name: &'static str) {
+        self.calls.borrow_mut().push(name);
+    }
+}
+
+impl Iterator for Probe<std::vec::IntoIter<u32>> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, u32) -> R,
+        R: std::ops::Try<Output = B>,
+    {
+        self.record("try_fold");
+        self.inner.try_fold(init, f)
+    }
+}
+
+impl ExactSizeIterator for Probe<std::vec::IntoIter<u32>> {
+    fn is_empty(&self) -> bool {
+        self.record("is_empty");
+        self.inner.is_empty()
+    }
+}
+
+impl Read for Probe<Cursor<Vec<u8>>> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.record("is_read_vectored");
+        true
+    }
+}
+
+impl BufRead for Probe<Cursor<Vec<u8>>> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+
+    fn has_data_left(&mut self) -> std::io::Result<bool> {
+        self.record("has_data_left");
+        self.inner.has_data_left()
+    }
+}
+
+#[implement(
+    newer_type_std::iter::nightly::Iterator,
+    newer_type_std::iter::nightly::ExactSizeIterator
+)]
+pub struct ProbedIter(Probe<std::vec::IntoIter<u32>>);
+
+#[implement(
+    newer_type_std::io::nightly::Read,
+    newer_type_std::io::nightly::BufRead
+)]
+pub struct ProbedIo(Probe<Cursor<Vec<u8>>>);
+
+#[test]
+fn test_unstable_iterator_overrides_reached() {
+    let (probe, calls) = Probe::new(vec![1, 2, 3].into_iter());
+    let mut probed = ProbedIter(probe);
+    assert_eq!(probed.try_fold(0u32, |a, n| a.checked_add(n)), Some(6));
+    assert!(probed.is_empty());
+    assert_eq!(*calls.borrow(), ["try_fold", "is_empty"]);
+}
+
+#[test]
+fn test_unstable_io_overrides_reached() {
+    let (probe, calls) = Probe::new(Cursor::new(b"ab".to_vec()));
+    let mut probed = ProbedIo(probe);
+    assert!(probed.is_read_vectored());
+    assert!(probed.has_data_left().unwrap());
+    assert_eq!(*calls.borrow(), ["is_read_vectored", "has_data_left"]);
+}
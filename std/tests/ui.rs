@@ -0,0 +1,7 @@
+// The expected messages are of the stable compiler. Regenerate them with
+// `TRYBUILD=overwrite cargo test --test ui` after updating the toolchain.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,7 @@
+use newer_type::implement;
+
+// Unknown names are left to rustc.
+#[implement(newer_type_std::fmt::Show)]
+pub struct Name(String);
+
+fn main() {}
@@ -0,0 +1,134 @@
+use newer_type::{implement, target};
+
+newer_type::repeater!();
+
+pub trait Scale<Rhs: ?Sized = Self> {
+    fn scale(&self, by: &Rhs) -> u32;
+    fn reset(&mut self);
+    fn into_value(self) -> u32
+    where
+        Self: Sized;
+    /// # Safety
+    ///
+    /// Never unsafe, only for the check of `unsafe fn`.
+    unsafe fn raw(&self) -> *const u32;
+}
+
+impl Scale for u32 {
+    fn scale(&self, by: &u32) -> u32 {
+        self * by
+    }
+
+    fn reset(&mut self) {
+        *self = 0;
+    }
+
+    fn into_value(self) -> u32 {
+        self
+    }
+
+    unsafe fn raw(&self) -> *const u32 {
+        self
+    }
+}
+
+impl Scale<str> for u32 {
+    fn scale(&self, by: &str) -> u32 {
+        self * by.len() as u32
+    }
+
+    fn reset(&mut self) {
+        *self = 0;
+    }
+
+    fn into_value(self) -> u32 {
+        self
+    }
+
+    unsafe fn raw(&self) -> *const u32 {
+        self
+    }
+}
+
+#[target(alternative = crate::Scale)]
+pub trait ScaleShim<Rhs: ?Sized = Self> {
+    fn scale(&self, by: &Rhs) -> u32;
+    fn reset(&mut self);
+    fn into_value(self) -> u32
+    where
+        Self: Sized;
+    /// # Safety
+    ///
+    /// Never unsafe, only for the check of `unsafe fn`.
+    unsafe fn raw(&self) -> *const u32;
+}
+
+#[implement(ScaleShim<u32>, ScaleShim<str>)]
+struct Wrapper(u32);
+
+#[test]
+fn test_checked_alternative() {
+    let mut wrapper = Wrapper(3);
+    assert_eq!(Scale::<u32>::scale(&wrapper, &2), 6);
+    assert_eq!(Scale::<str>::scale(&wrapper, "ab"), 6);
+    assert_eq!(unsafe { *Scale::<u32>::raw(&wrapper) }, 3);
+    Scale::<u32>::reset(&mut wrapper);
+    assert_eq!(Scale::<u32>::into_value(wrapper), 0);
+}
+
+#[target(alternative = ::core::cmp::PartialOrd, complete)]
+pub trait PartialOrd<Rhs: ?Sized = Self>: ::core::cmp::PartialEq<Rhs> {
+    fn partial_cmp(&self, other: &Rhs) -> ::core::option::Option<::core::cmp::Ordering>;
+    fn lt(&self, other: &Rhs) -> ::core::primitive::bool;
+    fn le(&self, other: &Rhs) -> ::core::primitive::bool;
+    fn gt(&self, other: &Rhs) -> ::core::primitive::bool;
+    fn ge(&self, other: &Rhs) -> ::core::primitive::bool;
+}
+
+struct Reversed(u32);
+
+impl PartialEq for Reversed {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl ::core::cmp::PartialOrd for Reversed {
+    fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+
+    // Inconsistent on purpose, to see that the override is reached
+    fn lt(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(PartialEq)]
+struct Key(Reversed);
+
+#[implement(PartialOrd)]
+struct Sorted(Key);
+
+impl PartialEq for Sorted {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl ::core::cmp::PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        self.0.lt(&other.0)
+    }
+}
+
+#[test]
+fn test_complete_alternative() {
+    let (a, b) = (Sorted(Key(Reversed(1))), Sorted(Key(Reversed(2))));
+    assert!(a > b);
+    assert!(a < b);
+}
@@ -0,0 +1,61 @@
+use newer_type::{implement, target};
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+pub struct Cat;
+
+impl Named for Cat {
+    fn name(&self) -> String {
+        "cat".to_owned()
+    }
+}
+
+pub struct Dog;
+
+impl Named for Dog {
+    fn name(&self) -> String {
+        "dog".to_owned()
+    }
+}
+
+#[implement(Named, assert_impl)]
+pub struct Pet {
+    animal: Cat,
+}
+
+#[implement(Named, assert_impl)]
+pub enum Animal {
+    Cat(Cat),
+    Dog { dog: Dog },
+}
+
+#[implement(Named, assert_impl)]
+pub struct Boxed<T>(T);
+
+#[implement]
+pub struct Tagged<'a, T: ?Sized, const N: usize>
+where
+    T: 'a,
+{
+    #[implement(Named, assert_impl)]
+    animal: Dog,
+    tag: ::core::marker::PhantomData<&'a T>,
+    ids: [u8; N],
+}
+
+#[test]
+fn test_assert_impl() {
+    assert_eq!(Pet { animal: Cat }.name(), "cat");
+    assert_eq!(Animal::Dog { dog: Dog }.name(), "dog");
+    assert_eq!(Boxed(Cat).name(), "cat");
+    let tagged = Tagged::<'_, str, 2> {
+        animal: Dog,
+        tag: ::core::marker::PhantomData,
+        ids: [0; 2],
+    };
+    assert_eq!(tagged.name(), "dog");
+    assert_eq!(tagged.ids, [0; 2]);
+}
@@ -0,0 +1,103 @@
+newer_type::repeater!();
+
+// `async fn` and `impl Trait` in traits are stable since Rust 1.75.
+#[rustversion::since(1.75)]
+mod since_1_75 {
+    use newer_type::{implement, target};
+    use std::future::Future;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        fn noop_raw_waker() -> RawWaker {
+            fn clone(_: *const ()) -> RawWaker {
+                noop_raw_waker()
+            }
+            fn noop(_: *const ()) {}
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[target]
+    trait Service {
+        async fn call(&self, request: ::core::primitive::u32) -> ::core::primitive::u32;
+        fn stream(&self) -> impl ::core::iter::Iterator<Item = ::core::primitive::u32>;
+        fn poll_ready(&mut self) -> impl ::core::future::Future<Output = ::core::primitive::bool>;
+    }
+
+    struct Doubler;
+
+    impl Service for Doubler {
+        async fn call(&self, request: u32) -> u32 {
+            request * 2
+        }
+
+        fn stream(&self) -> impl Iterator<Item = u32> {
+            vec![1, 2].into_iter()
+        }
+
+        fn poll_ready(&mut self) -> impl Future<Output = bool> {
+            std::future::ready(true)
+        }
+    }
+
+    struct Adder(u32);
+
+    impl Service for Adder {
+        async fn call(&self, request: u32) -> u32 {
+            request + self.0
+        }
+
+        fn stream(&self) -> impl Iterator<Item = u32> {
+            std::iter::repeat(self.0).take(3)
+        }
+
+        fn poll_ready(&mut self) -> impl Future<Output = bool> {
+            std::future::ready(false)
+        }
+    }
+
+    #[implement(Service)]
+    struct MyService(Adder);
+
+    #[implement(Service)]
+    enum AnyService {
+        Doubler(Doubler),
+        Adder(Adder),
+        Nested(MyService),
+    }
+
+    #[test]
+    fn test_async_struct() {
+        let mut service = MyService(Adder(1));
+        assert_eq!(block_on(service.call(2)), 3);
+        assert_eq!(service.stream().collect::<Vec<_>>(), vec![1, 1, 1]);
+        assert!(!block_on(service.poll_ready()));
+    }
+
+    #[test]
+    fn test_async_enum() {
+        let mut services = [
+            AnyService::Doubler(Doubler),
+            AnyService::Adder(Adder(5)),
+            AnyService::Nested(MyService(Adder(7))),
+        ];
+        let results = services
+            .iter()
+            .map(|service| block_on(service.call(10)))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![20, 15, 17]);
+        assert_eq!(services[0].stream().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(services[2].stream().collect::<Vec<_>>(), vec![7, 7, 7]);
+        assert!(block_on(services[0].poll_ready()));
+        assert!(!block_on(services[1].poll_ready()));
+    }
+}
@@ -0,0 +1,72 @@
+#![deny(deprecated)]
+use newer_type::{implement, target};
+use std::panic::Location;
+
+newer_type::repeater!();
+
+#[target]
+trait Locate {
+    /// Returns the location of the caller.
+    #[track_caller]
+    fn locate(&self) -> &'static Location<'static>;
+
+    #[deprecated]
+    #[allow(unused_variables)]
+    fn old(&self, x: ::core::primitive::u8);
+}
+
+impl Locate for () {
+    #[track_caller]
+    fn locate(&self) -> &'static Location<'static> {
+        Location::caller()
+    }
+
+    fn old(&self, _: u8) {}
+}
+
+#[implement(Locate)]
+struct Wrapper(());
+
+#[implement(Locate, inline = "never")]
+enum Never {
+    A(()),
+    B(Wrapper),
+}
+
+#[target(inline = "always")]
+trait Twice {
+    fn twice(&self) -> ::core::primitive::u32;
+}
+
+impl Twice for u32 {
+    fn twice(&self) -> u32 {
+        self * 2
+    }
+}
+
+#[implement(Twice)]
+struct Always(u32);
+
+#[implement(Twice, inline = "default")]
+struct Default(u32);
+
+#[test]
+fn test_track_caller() {
+    let (location, line) = (Wrapper(()).locate(), line!());
+    assert_eq!((location.file(), location.line()), (file!(), line));
+    let (location, line) = (Never::B(Wrapper(())).locate(), line!());
+    assert_eq!((location.file(), location.line()), (file!(), line));
+}
+
+#[test]
+fn test_inline() {
+    assert_eq!(Always(2).twice(), 4);
+    assert_eq!(Default(3).twice(), 6);
+    let _ = Never::A(());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated() {
+    Wrapper(()).old(1);
+}
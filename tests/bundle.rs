@@ -0,0 +1,63 @@
+use newer_type::{implement, target, target_bundle};
+
+newer_type::repeater!();
+
+#[target]
+pub trait Name {
+    fn name(&self) -> ::std::string::String;
+}
+
+#[target]
+pub trait Scale<T> {
+    fn scale(&self, factor: T) -> T;
+}
+
+#[target_bundle]
+pub trait Profile<T>: crate::Name + crate::Scale<T> {}
+
+#[target_bundle]
+pub trait Scaled: crate::Scale<::core::primitive::u32> {}
+
+pub struct Person(u32);
+
+impl Name for Person {
+    fn name(&self) -> String {
+        format!("person{}", self.0)
+    }
+}
+
+impl Scale<u32> for Person {
+    fn scale(&self, factor: u32) -> u32 {
+        self.0 * factor
+    }
+}
+
+#[implement(Profile<u32>)]
+pub struct Employee(Person);
+
+#[implement(Scaled)]
+pub enum Member {
+    Person(Person),
+    Fixed(Fixed),
+}
+
+pub struct Fixed;
+
+impl Scale<u32> for Fixed {
+    fn scale(&self, _factor: u32) -> u32 {
+        1
+    }
+}
+
+#[test]
+fn test_bundle() {
+    let employee = Employee(Person(3));
+    assert_eq!(employee.name(), "person3");
+    assert_eq!(employee.scale(2), 6);
+}
+
+#[test]
+fn test_bundle_enum() {
+    assert_eq!(Member::Person(Person(5)).scale(3), 15);
+    assert_eq!(Member::Fixed(Fixed).scale(3), 1);
+}
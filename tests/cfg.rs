@@ -0,0 +1,92 @@
+use newer_type::{implement, target};
+
+newer_type::repeater!();
+
+#[target]
+trait Shape {
+    fn area(&self) -> ::core::primitive::u32;
+
+    #[cfg(not(test))]
+    fn volume(&self) -> ::core::primitive::u32;
+}
+
+#[target]
+trait Named {
+    #[cfg(test)]
+    type Name;
+
+    #[cfg(not(test))]
+    type Name: ::core::default::Default;
+
+    fn name(&self) -> Self::Name;
+}
+
+impl Named for u32 {
+    type Name = &'static str;
+
+    fn name(&self) -> &'static str {
+        "u32"
+    }
+}
+
+impl Shape for u32 {
+    fn area(&self) -> u32 {
+        self * self
+    }
+}
+
+#[allow(dead_code)]
+#[target]
+trait Unimplemented {
+    fn never(&self);
+}
+
+#[implement(Shape, Named, #[cfg(not(test))] Unimplemented)]
+struct Square(u32);
+
+#[implement(#[cfg(test)] Shape)]
+enum AnyShape {
+    Square(Square),
+    #[allow(dead_code)]
+    Raw(#[implement(#[cfg(not(test))] Unimplemented)] u32),
+}
+
+// The same trait under the exclusive conditions
+#[implement]
+struct Tile(
+    #[implement(#[cfg(not(test))] Shape)]
+    #[implement(#[cfg(test)] Shape)]
+    u32,
+);
+
+#[test]
+fn test_cfg() {
+    assert_eq!(Square(3).area(), 9);
+    assert_eq!(Square(3).name(), "u32");
+    assert_eq!(AnyShape::Square(Square(2)).area(), 4);
+    assert_eq!(Tile(5).area(), 25);
+}
+
+#[target(alternative = ::core::iter::Iterator)]
+trait Iter {
+    type Item;
+    fn next(&mut self) -> ::core::option::Option<Self::Item>;
+
+    #[rustversion::since(1.0)]
+    fn count(self) -> ::core::primitive::usize
+    where
+        Self: ::core::marker::Sized;
+
+    // Neither checked nor forwarded with this compiler
+    #[rustversion::before(1.0)]
+    fn never(&self);
+}
+
+#[implement(Iter)]
+struct Countdown(::core::ops::Range<u32>);
+
+#[test]
+fn test_rustversion() {
+    assert_eq!(Countdown(0..3).count(), 3);
+    assert_eq!(Countdown(0..3).next(), Some(0));
+}
@@ -0,0 +1,83 @@
+use newer_type::{target, Implement, Newtype};
+
+newer_type::repeater!();
+
+#[target]
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+#[target]
+trait Named {
+    fn name(&self) -> String;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Named for String {
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+// 1. Traits given on the item
+#[derive(Implement)]
+#[implement(Shape, newtype)]
+struct Tile(Square);
+
+#[test]
+fn test_derive_item() {
+    let tile = Tile::new(Square(3));
+    assert_eq!(tile.area(), 9);
+    assert_eq!(tile.into_inner().0, 3);
+}
+
+// 2. Traits given on the fields, with other derives in any order
+#[derive(Debug, Implement, Clone)]
+struct Labeled {
+    #[implement(Named)]
+    label: String,
+    count: u32,
+}
+
+#[test]
+fn test_derive_fields() {
+    let labeled = Labeled {
+        label: "foo".to_string(),
+        count: 1,
+    };
+    assert_eq!(labeled.clone().name(), "foo");
+    assert_eq!(labeled.count, 1);
+}
+
+// 3. Enums
+#[derive(Implement)]
+#[implement(Shape)]
+enum AnyShape {
+    Square(Square),
+    Tile(Tile),
+}
+
+#[test]
+fn test_derive_enum() {
+    assert_eq!(AnyShape::Square(Square(2)).area(), 4);
+    assert_eq!(AnyShape::Tile(Tile(Square(4))).area(), 16);
+}
+
+// 4. Transparent with explicit representation
+#[derive(Implement, Debug, PartialEq)]
+#[implement(transparent)]
+#[repr(transparent)]
+struct Meters(f64);
+
+#[test]
+fn test_derive_transparent() {
+    assert_eq!(Meters::from_ref(&1.5), &Meters(1.5));
+    assert_eq!(Meters(2.0).into_inner(), 2.0);
+}
@@ -0,0 +1,159 @@
+use newer_type::implement;
+
+mod other {
+    pub trait Greet {
+        fn greet(&self, name: &str) -> String;
+        fn shout(&self, name: &str) -> String {
+            self.greet(name).to_uppercase()
+        }
+    }
+
+    pub trait Scale<T> {
+        fn scale(&mut self, factor: T);
+    }
+
+    pub struct English;
+
+    impl Greet for English {
+        fn greet(&self, name: &str) -> String {
+            format!("Hello, {}", name)
+        }
+    }
+
+    impl Scale<u32> for Vec<u32> {
+        fn scale(&mut self, factor: u32) {
+            self.iter_mut().for_each(|n| *n *= factor);
+        }
+    }
+
+    impl Scale<u32> for u32 {
+        fn scale(&mut self, factor: u32) {
+            *self *= factor;
+        }
+    }
+}
+
+// 1. Foreign trait declared inline, with a default method left out
+#[implement(extern other::Greet {
+    fn greet(&self, name: &str) -> String;
+})]
+struct Speaker(other::English);
+
+#[test]
+fn test_extern_trait() {
+    use other::Greet;
+    let speaker = Speaker(other::English);
+    assert_eq!(speaker.greet("Bob"), "Hello, Bob");
+    assert_eq!(speaker.shout("Bob"), "HELLO, BOB");
+}
+
+// 2. Generic arguments, enums and the standard library traits
+#[implement(
+    extern other::Scale<u32> {
+        fn scale(&mut self, factor: u32);
+    },
+    extern ::core::fmt::Display {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result;
+    }
+)]
+enum Amount {
+    One(u32),
+    Many(u32),
+}
+
+#[test]
+fn test_extern_enum() {
+    use other::Scale;
+    let mut amount = Amount::Many(3);
+    amount.scale(2);
+    assert_eq!(amount.to_string(), "6");
+    assert_eq!(Amount::One(1).to_string(), "1");
+}
+
+// 3. Declared on the field
+#[implement]
+struct Labels {
+    #[implement(extern other::Scale<u32> { fn scale(&mut self, factor: u32); })]
+    values: Vec<u32>,
+    _name: &'static str,
+}
+
+#[test]
+fn test_extern_field() {
+    use other::Scale;
+    let mut labels = Labels {
+        values: vec![1, 2],
+        _name: "labels",
+    };
+    labels.scale(3);
+    assert_eq!(labels.values, vec![3, 6]);
+}
+
+// 4. Generic remote trait, implemented for every parameter
+mod generic {
+    pub trait Convert<T> {
+        fn convert(&self) -> T;
+    }
+
+    pub struct Seven;
+
+    impl<T: From<u8>> Convert<T> for Seven {
+        fn convert(&self) -> T {
+            T::from(7)
+        }
+    }
+}
+
+#[implement(for<T: From<u8>> extern generic::Convert<T> { fn convert(&self) -> T; })]
+struct Converter(generic::Seven);
+
+#[test]
+fn test_extern_generic() {
+    use generic::Convert;
+    let n: u32 = Converter(generic::Seven).convert();
+    let m: u64 = Converter(generic::Seven).convert();
+    assert_eq!((n, m), (7, 7));
+}
+
+// 5. Const parameters of other types than `usize`
+mod slot {
+    pub trait Slot<const N: u8> {
+        fn index(&self) -> u8 {
+            N
+        }
+        fn name(&self) -> &'static str;
+    }
+
+    pub trait Flag<const B: bool> {
+        fn name(&self) -> &'static str;
+    }
+
+    pub struct Drawer;
+
+    impl<const N: u8> Slot<N> for Drawer {
+        fn name(&self) -> &'static str {
+            "drawer"
+        }
+    }
+
+    impl Flag<true> for Drawer {
+        fn name(&self) -> &'static str {
+            "flagged drawer"
+        }
+    }
+}
+
+#[implement(
+    for<const N: u8> extern slot::Slot<N> { fn name(&self) -> &'static str; },
+    extern slot::Flag<true> { fn name(&self) -> &'static str; }
+)]
+struct Cabinet(slot::Drawer);
+
+#[test]
+fn test_extern_const() {
+    use slot::{Flag, Slot};
+    let cabinet = Cabinet(slot::Drawer);
+    assert_eq!(<Cabinet as Slot<4>>::index(&cabinet), 4);
+    assert_eq!(<Cabinet as Slot<200>>::name(&cabinet), "drawer");
+    assert_eq!(<Cabinet as Flag<true>>::name(&cabinet), "flagged drawer");
+}
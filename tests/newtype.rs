@@ -0,0 +1,100 @@
+use newer_type::{implement, Newtype};
+use std::marker::PhantomData;
+
+// 1. Tuple struct
+#[implement(newtype)]
+#[derive(Debug, PartialEq)]
+struct Meters(f64);
+
+#[test]
+fn test_tuple_newtype() {
+    let mut m = Meters::new(1.5);
+    assert_eq!(*m.as_inner(), 1.5);
+    *m.as_inner_mut() += 1.0;
+    assert_eq!(m.into_inner(), 2.5);
+    assert_eq!(Meters::from(3.0), Meters(3.0));
+    let inner: f64 = Meters(4.0).into();
+    assert_eq!(inner, 4.0);
+}
+
+// 2. Named struct with marker field
+#[implement(newtype)]
+struct Id<T> {
+    value: u64,
+    _marker: PhantomData<T>,
+}
+
+#[test]
+fn test_named_newtype() {
+    let id: Id<String> = 42.into();
+    assert_eq!(*id.as_inner(), 42);
+    assert_eq!(u64::from(Id::<String>::new(7)), 7);
+}
+
+// 3. Generic inner type
+#[implement(newtype)]
+pub struct Wrapper<T>(T);
+
+#[test]
+fn test_generic_newtype() {
+    let w = Wrapper::from(vec![1, 2, 3]);
+    assert_eq!(w.as_inner().len(), 3);
+    assert_eq!(w.into_inner(), vec![1, 2, 3]);
+}
+
+// 4. Inner types not covering the type parameter, which cannot be converted
+// into by `From`
+#[implement(newtype)]
+pub struct Boxed<T>(Box<T>);
+
+#[implement(newtype)]
+pub struct Borrowed<'a, T>(&'a T);
+
+#[test]
+fn test_uncovered_newtype() {
+    let boxed = Boxed::from(Box::new(1));
+    assert_eq!(*boxed.into_inner(), 1);
+    let borrowed = Borrowed::from(&2);
+    assert_eq!(**borrowed.as_inner(), 2);
+}
+
+// 5. Combined with validation
+fn is_short(s: &str) -> bool {
+    s.len() < 8
+}
+
+#[implement(newtype, validate = is_short)]
+struct ShortString(String);
+
+#[test]
+fn test_validated_newtype() {
+    let s = ShortString::new("abc".to_owned()).unwrap();
+    assert_eq!(s.as_inner(), "abc");
+    assert_eq!(String::from(s), "abc");
+    assert!(ShortString::try_from("too long string".to_owned()).is_err());
+}
+
+// 6. `Newtype` trait
+fn double_all<T: Newtype<Inner = f64>>(values: Vec<T>) -> Vec<T> {
+    values
+        .into_iter()
+        .map(|v| T::from_inner(v.into_inner() * 2.0))
+        .collect()
+}
+
+#[implement]
+struct Seconds(f64);
+
+#[test]
+fn test_newtype_trait() {
+    let doubled = double_all(vec![Seconds(1.0), Seconds(2.5)]);
+    assert_eq!(
+        doubled.iter().map(|s| s.0).collect::<Vec<_>>(),
+        vec![2.0, 5.0]
+    );
+    let mut m = Meters(1.0);
+    *Newtype::inner_mut(&mut m) = 2.0;
+    assert_eq!(*Newtype::inner(&m), 2.0);
+    let id: Id<()> = Newtype::from_inner(3);
+    assert_eq!(Newtype::into_inner(id), 3);
+}
@@ -0,0 +1,76 @@
+use newer_type::implement;
+
+pub trait Repeater<const TRAIT_ID: u64, const NTH: usize, T: ?Sized> {
+    type Type;
+}
+
+// Identical trait definitions in different modules should get distinct nonces.
+mod a {
+    use newer_type::target;
+
+    #[target(repeater = crate::Repeater)]
+    pub trait Value {
+        fn value(&self) -> ::core::primitive::u32;
+    }
+
+    impl Value for u32 {
+        fn value(&self) -> u32 {
+            *self
+        }
+    }
+}
+
+mod b {
+    use newer_type::target;
+
+    #[target(repeater = crate::Repeater)]
+    pub trait Value {
+        fn value(&self) -> ::core::primitive::u32;
+    }
+
+    impl Value for u32 {
+        fn value(&self) -> u32 {
+            *self * 2
+        }
+    }
+}
+
+#[implement(a::Value, b::Value)]
+pub struct Number(u32);
+
+#[test]
+fn test_identical_traits() {
+    assert_eq!(a::Value::value(&Number(3)), 3);
+    assert_eq!(b::Value::value(&Number(3)), 6);
+}
+
+// Identical trait definitions emitted by a macro share the location too.
+macro_rules! emit_scaled_trait {
+    ($($module:ident => $factor:literal),*) => {$(
+        mod $module {
+            use newer_type::target;
+
+            #[target(repeater = crate::Repeater)]
+            pub trait Scaled {
+                fn scaled(&self) -> ::core::primitive::u32;
+            }
+
+            impl Scaled for u32 {
+                fn scaled(&self) -> u32 {
+                    *self * $factor
+                }
+            }
+        }
+    )*};
+}
+
+emit_scaled_trait!(c => 3, d => 4);
+
+#[implement(c::Scaled, d::Scaled)]
+pub struct Scalable(u32);
+
+#[test]
+fn test_identical_traits_from_macro() {
+    assert_eq!(c::Scaled::scaled(&Scalable(2)), 6);
+    assert_eq!(d::Scaled::scaled(&Scalable(2)), 8);
+}
@@ -0,0 +1,63 @@
+use newer_type::{implement, target};
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+impl Named for String {
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+pub struct Legacy(String);
+
+// The tokens passed by `#[implement]` of the previous protocol, which lacks the
+// version header.
+Named! { (Named) pub struct Legacy(String); }
+
+pub struct Current(String);
+
+Named! { version = 2, (Named) pub struct Current(String); }
+
+// A target trait declared by the previous protocol, whose macro accepts only
+// the tokens without the version header. Such a trait also lacks the marker of
+// `#[target]`, which is warned about.
+#[allow(deprecated)]
+mod legacy {
+    use newer_type::implement;
+
+    pub trait Greeting {
+        fn greeting(&self) -> ::std::string::String;
+    }
+
+    macro_rules! Greeting {
+        ((Greeting) $vis:vis struct $name:ident($field_vis:vis $inner:ty);) => {
+            impl Greeting for $name {
+                fn greeting(&self) -> ::std::string::String {
+                    format!("Hello, {}", self.0)
+                }
+            }
+        };
+    }
+
+    #[implement(Greeting)]
+    pub struct Greeter(pub String);
+}
+
+// The options added since the previous protocol require the version header.
+#[implement(Named, inline = "never")]
+pub struct Tagged(String);
+
+#[test]
+fn test_protocol_versions() {
+    use legacy::Greeting;
+    assert_eq!(Legacy("old".to_owned()).name(), "old");
+    assert_eq!(Current("new".to_owned()).name(), "new");
+    assert_eq!(Tagged("tag".to_owned()).name(), "tag");
+    assert_eq!(
+        legacy::Greeter("world".to_owned()).greeting(),
+        "Hello, world"
+    );
+}
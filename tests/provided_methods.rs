@@ -0,0 +1,281 @@
+// Compares the provided methods listed in `macro/diagnostics.rs` for
+// `#[target(complete)]` with the traits in the sources of the toolchain, so
+// that the methods stabilized later are noticed. The sources are read from
+// `RUST_SRC_PATH` (the `library` directory), or from the `rust-src` component.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use syn::{Expr, Item, Lit};
+
+/// The files declaring the traits, relative to `library`. Some traits moved
+/// between the toolchains, so the candidates are tried in order.
+const TRAIT_FILES: &[(&str, &str, &[&str])] = &[
+    ("cmp", "PartialEq", &["core/src/cmp.rs"]),
+    ("cmp", "PartialOrd", &["core/src/cmp.rs"]),
+    ("cmp", "Ord", &["core/src/cmp.rs"]),
+    ("error", "Error", &["core/src/error.rs", "std/src/error.rs"]),
+    ("fmt", "Write", &["core/src/fmt/mod.rs"]),
+    ("hash", "BuildHasher", &["core/src/hash/mod.rs"]),
+    ("hash", "Hasher", &["core/src/hash/mod.rs"]),
+    ("io", "Read", &["std/src/io/mod.rs"]),
+    ("io", "Write", &["std/src/io/mod.rs"]),
+    ("io", "BufRead", &["std/src/io/mod.rs"]),
+    ("io", "Seek", &["std/src/io/mod.rs"]),
+    ("iter", "Iterator", &["core/src/iter/traits/iterator.rs"]),
+    (
+        "iter",
+        "DoubleEndedIterator",
+        &["core/src/iter/traits/double_ended.rs"],
+    ),
+    (
+        "iter",
+        "ExactSizeIterator",
+        &["core/src/iter/traits/exact_size.rs"],
+    ),
+    ("iter", "FusedIterator", &["core/src/iter/traits/marker.rs"]),
+];
+
+/// The stable provided methods which cannot be forwarded, and are therefore
+/// left out of the list: the ones mentioning `Self` other than the receiver
+/// or taking `self` by value with `Self: Sized`, and the ones with the `Try`
+/// bound, which is unstable to name.
+const EXCLUDED: &[(&str, &str, &[&str])] = &[
+    ("cmp", "Ord", &["max", "min", "clamp"]),
+    ("io", "Read", &["by_ref", "bytes", "chain", "take"]),
+    ("io", "Write", &["by_ref"]),
+    ("io", "BufRead", &["split", "lines"]),
+    (
+        "iter",
+        "Iterator",
+        &[
+            "step_by",
+            "chain",
+            "zip",
+            "map",
+            "filter",
+            "filter_map",
+            "enumerate",
+            "peekable",
+            "skip_while",
+            "take_while",
+            "map_while",
+            "skip",
+            "take",
+            "scan",
+            "flat_map",
+            "flatten",
+            "fuse",
+            "inspect",
+            "by_ref",
+            "try_fold",
+            "try_for_each",
+            "rposition",
+            "rev",
+            "unzip",
+            "copied",
+            "cloned",
+            "cycle",
+            "cmp_by",
+            "partial_cmp_by",
+            "eq_by",
+        ],
+    ),
+    ("iter", "DoubleEndedIterator", &["try_rfold"]),
+];
+
+fn library_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("RUST_SRC_PATH") {
+        return Some(PathBuf::from(dir));
+    }
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()?;
+    let sysroot = String::from_utf8(output.stdout).ok()?;
+    let dir = Path::new(sysroot.trim()).join("lib/rustlib/src/rust/library");
+    dir.exists().then_some(dir)
+}
+
+/// The `(module, trait, methods)` entries of `PROVIDED_METHODS`.
+fn listed_methods() -> Vec<(String, String, Vec<String>)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("macro/diagnostics.rs");
+    let file = syn::parse_file(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let item = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Const(item) if item.ident == "PROVIDED_METHODS" => Some(item),
+            _ => None,
+        })
+        .expect("`PROVIDED_METHODS` is not found");
+    fn strings(expr: &Expr) -> Vec<String> {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Str(s) => vec![s.value()],
+                _ => vec![],
+            },
+            Expr::Reference(r) => strings(&r.expr),
+            Expr::Array(array) => array.elems.iter().flat_map(strings).collect(),
+            _ => vec![],
+        }
+    }
+    let Expr::Reference(array) = &*item.expr else {
+        panic!("`PROVIDED_METHODS` is not a slice");
+    };
+    let Expr::Array(array) = &*array.expr else {
+        panic!("`PROVIDED_METHODS` is not a slice");
+    };
+    array
+        .elems
+        .iter()
+        .map(|elem| {
+            let Expr::Tuple(tuple) = elem else {
+                panic!("unexpected entry in `PROVIDED_METHODS`");
+            };
+            let module = strings(&tuple.elems[0]).remove(0);
+            let name = strings(&tuple.elems[1]).remove(0);
+            (module, name, strings(&tuple.elems[2]))
+        })
+        .collect()
+}
+
+/// Blank out the comments, strings and character literals, which may contain
+/// unbalanced braces.
+fn strip(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i += 1;
+                out.push_str("\"\"");
+            }
+            '\'' if chars.get(i + 1) == Some(&'\\') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+                out.push_str("' '");
+            }
+            '\'' if chars.get(i + 2) == Some(&'\'') => {
+                i += 3;
+                out.push_str("' '");
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// The stable methods with default implementations of `pub trait name` in
+/// `source`, or `None` if the trait is not declared there.
+fn stable_provided_methods(source: &str, name: &str) -> Option<Vec<String>> {
+    let source = strip(source);
+    let start = source.lines().scan(0, |offset, line| {
+        let found = *offset;
+        *offset += line.len() + 1;
+        Some((found, line))
+    });
+    let (offset, _) = start.into_iter().find(|(_, line)| {
+        let line = line.trim_start();
+        ["pub trait ", "pub const trait ", "pub unsafe trait "]
+            .iter()
+            .any(|prefix| {
+                line.strip_prefix(prefix).map_or(false, |rest| {
+                    rest.starts_with(name)
+                        && !rest[name.len()..].starts_with(|c: char| c.is_alphanumeric())
+                })
+            })
+    })?;
+    let body = &source[offset..];
+    let body = &body[body.find('{')? + 1..];
+    let mut methods = Vec::new();
+    let mut depth = 0;
+    // The attributes and the signature of the current item.
+    let mut header = String::new();
+    for c in body.chars() {
+        match (c, depth) {
+            ('}', 0) => break,
+            ('{', 0) => {
+                // The body of a provided method.
+                let ident = header.split_once("fn ").map(|(_, rest)| {
+                    rest.chars()
+                        .take_while(|c| c.is_alphanumeric() || *c == '_')
+                        .collect::<String>()
+                });
+                if let Some(ident) = ident {
+                    if header.contains("#[stable(") && !header.contains("#[unstable(") {
+                        methods.push(ident);
+                    }
+                }
+                depth += 1;
+            }
+            ('{', _) => depth += 1,
+            ('}', 1) => {
+                depth -= 1;
+                header.clear();
+            }
+            ('}', _) => depth -= 1,
+            (';', 0) => header.clear(),
+            (c, 0) => header.push(c),
+            _ => (),
+        }
+    }
+    Some(methods)
+}
+
+#[test]
+fn test_provided_methods_up_to_date() {
+    let Some(library) = library_dir() else {
+        eprintln!("skipped: install `rust-src` or set `RUST_SRC_PATH` to compare with the traits");
+        return;
+    };
+    let listed = listed_methods();
+    let mut unknown = Vec::new();
+    for (module, name, files) in TRAIT_FILES {
+        let methods = files
+            .iter()
+            .filter_map(|file| std::fs::read_to_string(library.join(file)).ok())
+            .find_map(|source| stable_provided_methods(&source, name))
+            .unwrap_or_else(|| panic!("`{module}::{name}` is not found in {}", library.display()));
+        let (_, _, listed) = listed
+            .iter()
+            .find(|(m, t, _)| m == module && t == name)
+            .unwrap_or_else(|| panic!("`{module}::{name}` is not in `PROVIDED_METHODS`"));
+        let excluded = EXCLUDED
+            .iter()
+            .find(|(m, t, _)| m == module && t == name)
+            .map_or(&[][..], |(_, _, methods)| *methods);
+        unknown.extend(
+            methods
+                .into_iter()
+                .filter(|method| !listed.contains(method) && !excluded.contains(&method.as_str()))
+                .map(|method| format!("{module}::{name}::{method}")),
+        );
+    }
+    assert!(
+        unknown.is_empty(),
+        "the provided methods not in `PROVIDED_METHODS` nor in `EXCLUDED`: {unknown:?}"
+    );
+}
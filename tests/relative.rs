@@ -0,0 +1,111 @@
+use newer_type::implement;
+
+newer_type::repeater!();
+
+pub(crate) trait Marker {}
+
+impl Marker for u8 {}
+
+mod model {
+    #[derive(Debug, PartialEq)]
+    pub(crate) struct Meters(pub u32);
+
+    pub(crate) struct Label(pub &'static str);
+
+    pub mod traits {
+        use newer_type::target;
+
+        #[target]
+        pub(crate) trait Measure {
+            fn length(&self) -> super::Meters;
+            fn label(&self, prefix: &str) -> self::Describe;
+            fn tagged<T: Clone + crate::Marker>(&self, tag: T) -> Option<(T, super::Meters)>;
+            fn name(&self) -> &str;
+            fn apply(&self, f: &dyn Fn(u32) -> u32) -> u32;
+        }
+
+        pub(crate) struct Describe(pub String);
+
+        impl Measure for super::Label {
+            fn length(&self) -> super::Meters {
+                super::Meters(self.0.len() as u32)
+            }
+
+            fn label(&self, prefix: &str) -> Describe {
+                Describe(format!("{}{}", prefix, self.0))
+            }
+
+            fn tagged<T: Clone + crate::Marker>(&self, tag: T) -> Option<(T, super::Meters)> {
+                Some((tag, self.length()))
+            }
+
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn apply(&self, f: &dyn Fn(u32) -> u32) -> u32 {
+                f(self.0.len() as u32)
+            }
+        }
+    }
+}
+
+mod collection {
+    use newer_type::target;
+
+    #[target]
+    pub trait Collection {
+        type Item;
+        fn items(&self) -> Option<Vec<Self::Item>>;
+        fn pairs(&self) -> Vec<(Self::Item, <Self as Collection>::Item)>;
+        fn first(&self) -> Option<&Self::Item>;
+    }
+
+    impl Collection for Vec<u8> {
+        type Item = u8;
+
+        fn items(&self) -> Option<Vec<u8>> {
+            Some(self.clone())
+        }
+
+        fn pairs(&self) -> Vec<(u8, u8)> {
+            self.iter().map(|n| (*n, *n)).collect()
+        }
+
+        fn first(&self) -> Option<&u8> {
+            <[u8]>::first(self)
+        }
+    }
+}
+
+mod wrapper {
+    use super::model::traits::Measure;
+    use newer_type::implement;
+
+    #[implement(Measure)]
+    pub(crate) struct Named(pub crate::model::Label);
+}
+
+#[implement(collection::Collection)]
+pub struct Bytes(Vec<u8>);
+
+#[test]
+fn test_private_and_relative_types() {
+    use model::traits::Measure;
+    use model::Meters;
+    let named = wrapper::Named(model::Label("cable"));
+    assert_eq!(named.length(), Meters(5));
+    assert_eq!(named.label("a ").0, "a cable");
+    assert_eq!(named.tagged(1u8), Some((1u8, Meters(5))));
+    assert_eq!(named.name(), "cable");
+    assert_eq!(named.apply(&|n| n * 2), 10);
+}
+
+#[test]
+fn test_self_projections() {
+    use collection::Collection;
+    let bytes = Bytes(vec![1, 2]);
+    assert_eq!(bytes.items(), Some(vec![1, 2]));
+    assert_eq!(bytes.pairs(), vec![(1, 1), (2, 2)]);
+    assert_eq!(bytes.first(), Some(&1));
+}
@@ -0,0 +1,79 @@
+use newer_type::implement;
+
+newer_type::repeater!();
+
+mod shapes {
+    use newer_type::target;
+
+    #[derive(Debug, PartialEq)]
+    pub struct Length(pub f64);
+
+    #[target]
+    pub trait Perimeter {
+        fn perimeter(&self) -> Length;
+        fn scaled(&self, factor: Length) -> Length;
+    }
+
+    pub struct Square(pub f64);
+
+    impl Perimeter for Square {
+        fn perimeter(&self) -> Length {
+            Length(self.0 * 4.0)
+        }
+
+        fn scaled(&self, factor: Length) -> Length {
+            Length(self.0 * 4.0 * factor.0)
+        }
+    }
+}
+
+mod tiles {
+    use crate::shapes::{Perimeter, Square};
+    use newer_type::implement;
+
+    #[implement(Perimeter)]
+    pub struct Tile(pub Square);
+}
+
+#[implement(shapes::Perimeter)]
+pub struct Frame(shapes::Square);
+
+// Not `pub`, so carried by the crate-visible repeater
+mod units {
+    use newer_type::target;
+
+    #[derive(Debug, PartialEq)]
+    pub(crate) struct Meters(pub(crate) u32);
+
+    #[target]
+    pub(crate) trait Height {
+        fn height(&self) -> Meters;
+    }
+
+    impl Height for u32 {
+        fn height(&self) -> Meters {
+            Meters(*self)
+        }
+    }
+}
+
+mod buildings {
+    use crate::units::Height;
+    use newer_type::implement;
+
+    #[implement(Height)]
+    pub(crate) struct Tower(pub(crate) u32);
+}
+
+#[test]
+fn test_default_repeater() {
+    use shapes::{Length, Perimeter};
+    assert_eq!(tiles::Tile(shapes::Square(1.5)).perimeter(), Length(6.0));
+    assert_eq!(Frame(shapes::Square(2.0)).scaled(Length(0.5)), Length(4.0));
+}
+
+#[test]
+fn test_default_repeater_for_crate_visible_trait() {
+    use units::{Height, Meters};
+    assert_eq!(buildings::Tower(30).height(), Meters(30));
+}
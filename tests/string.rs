@@ -1,10 +1,8 @@
 use newer_type::{implement, target};
 
-pub trait Repeater<const TRAIT_ID: u64, const NTH: usize, T: ?Sized> {
-    type Type;
-}
+newer_type::repeater!();
 
-#[target(alternative = ::std::string::ToString, newer_type = ::newer_type, repeater = Repeater)]
+#[target(alternative = ::std::string::ToString, newer_type = ::newer_type)]
 pub trait ToString {
     fn to_string(&self) -> String;
 }
@@ -0,0 +1,57 @@
+use newer_type::implement;
+
+// Supertraits declared in other modules, or which are not target traits, are
+// not delegated by `with_supertraits`.
+pub mod a {
+    use newer_type::target;
+
+    #[target]
+    pub trait Value {
+        fn value(&self) -> ::core::primitive::u32;
+    }
+
+    #[target]
+    pub trait Doubled: crate::a::Value + ::core::fmt::Debug {
+        fn doubled(&self) -> ::core::primitive::u32;
+    }
+
+    impl Value for u32 {
+        fn value(&self) -> u32 {
+            *self
+        }
+    }
+
+    impl Doubled for u32 {
+        fn doubled(&self) -> u32 {
+            *self * 2
+        }
+    }
+}
+
+pub mod b {
+    use newer_type::target;
+
+    #[target]
+    pub trait Value {
+        fn value(&self) -> ::core::primitive::u32;
+    }
+
+    impl Value for u32 {
+        fn value(&self) -> u32 {
+            *self + 100
+        }
+    }
+}
+
+#[implement(b::Value)]
+#[implement(a::Doubled, with_supertraits)]
+#[derive(Debug)]
+pub struct Number(u32);
+
+#[test]
+fn test_supertraits_in_same_module() {
+    assert_eq!(a::Doubled::doubled(&Number(3)), 6);
+    assert_eq!(a::Value::value(&Number(3)), 3);
+    assert_eq!(b::Value::value(&Number(3)), 103);
+    assert_eq!(format!("{:?}", Number(3)), "Number(3)");
+}
@@ -1,12 +1,10 @@
 use newer_type::{implement, target};
 
-pub trait Repeater<const TRAIT_ID: u64, const NTH: usize, T: ?Sized> {
-    type Type;
-}
+newer_type::repeater!();
 
 mod m {
     type T = usize;
-    #[super::target(repeater = crate::Repeater)]
+    #[super::target]
     pub trait MyNewTrait {
         type MyType<'a>
         where
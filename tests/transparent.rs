@@ -0,0 +1,121 @@
+use newer_type::{implement, target, Newtype};
+use std::marker::PhantomData;
+
+// 1. Sized inner type
+#[implement(transparent)]
+#[derive(Debug, PartialEq)]
+struct Meters(f64);
+
+#[test]
+fn test_transparent_ref() {
+    let mut value = 1.5;
+    assert_eq!(Meters::from_ref(&value), &Meters(1.5));
+    Meters::from_mut(&mut value).0 = 2.5;
+    assert_eq!(value, 2.5);
+    assert_eq!(Meters(3.0).into_inner(), 3.0);
+}
+
+#[test]
+fn test_transparent_slice() {
+    let mut values = [1.0, 2.0];
+    assert_eq!(Meters::from_slice(&values), &[Meters(1.0), Meters(2.0)]);
+    Meters::from_slice_mut(&mut values)[1].0 = 4.0;
+    assert_eq!(values, [1.0, 4.0]);
+}
+
+#[test]
+fn test_transparent_boxed() {
+    let boxed = Meters::from_boxed(Box::new(1.0));
+    assert_eq!(*boxed, Meters(1.0));
+    assert_eq!(*boxed.into_boxed_inner(), 1.0);
+}
+
+// 2. Unsized inner type, with zero-sized marker field
+#[implement(transparent)]
+#[repr(transparent)]
+struct Name<T> {
+    _marker: PhantomData<T>,
+    value: str,
+}
+
+#[test]
+fn test_transparent_unsized() {
+    let name: &Name<u8> = Name::from_ref("foo");
+    assert_eq!(&name.value, "foo");
+    let boxed: Box<Name<u8>> = Name::from_boxed("bar".into());
+    assert_eq!(&*boxed.into_boxed_inner(), "bar");
+}
+
+#[implement(transparent, newtype)]
+struct Bytes([u8]);
+
+#[test]
+fn test_transparent_newtype_unsized() {
+    let mut data = [1, 2, 3];
+    let bytes = Bytes::from_mut(&mut data);
+    bytes.as_inner_mut()[0] = 4;
+    assert_eq!(bytes.as_inner(), &[4, 2, 3]);
+}
+
+// 3. Trait implementations for unsized wrappers
+newer_type::repeater!();
+
+#[target]
+trait Describe {
+    fn describe(&self) -> String;
+    fn len_mut(&mut self) -> usize;
+    fn into_description(self) -> String
+    where
+        Self: ::core::marker::Sized;
+}
+
+#[target]
+trait Inspect {
+    fn inspect(&self) -> String;
+}
+
+impl<T: ?Sized + Describe> Inspect for T {
+    fn inspect(&self) -> String {
+        self.describe()
+    }
+}
+
+impl Describe for str {
+    fn describe(&self) -> String {
+        format!("str({})", self)
+    }
+
+    fn len_mut(&mut self) -> usize {
+        self.len()
+    }
+}
+
+impl Describe for String {
+    fn describe(&self) -> String {
+        format!("String({})", self)
+    }
+
+    fn len_mut(&mut self) -> usize {
+        self.len()
+    }
+
+    fn into_description(self) -> String {
+        self
+    }
+}
+
+#[implement(Describe, transparent)]
+struct Label(str);
+
+#[implement(Inspect)]
+struct Wrapper<T: ?Sized>(T);
+
+#[test]
+fn test_unsized_trait() {
+    assert_eq!(Label::from_ref("foo").describe(), "str(foo)");
+    assert_eq!(String::from("foo").into_description(), "foo");
+    let wrapper: Box<Wrapper<dyn Describe>> = Box::new(Wrapper(String::from("bar")));
+    assert_eq!(wrapper.inspect(), "String(bar)");
+    let mut label = String::from("baz");
+    assert_eq!(Label::from_mut(label.as_mut_str()).len_mut(), 3);
+}
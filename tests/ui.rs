@@ -0,0 +1,16 @@
+// The expected messages are of the stable compiler. Regenerate them with
+// `TRYBUILD=overwrite cargo test --test ui` after updating the toolchain.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
+
+// `#[diagnostic::on_unimplemented]` used by `assert_impl` requires Rust 1.78.
+#[rustversion::since(1.78)]
+#[test]
+fn ui_since_1_78() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/since_1_78/*.rs");
+}
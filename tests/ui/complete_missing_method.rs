@@ -0,0 +1,9 @@
+use newer_type::target;
+
+#[target(alternative = ::core::cmp::PartialOrd, complete)]
+pub trait PartialOrd<Rhs: ?Sized = Self>: ::core::cmp::PartialEq<Rhs> {
+    fn partial_cmp(&self, other: &Rhs) -> ::core::option::Option<::core::cmp::Ordering>;
+    fn lt(&self, other: &Rhs) -> ::core::primitive::bool;
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+use newer_type::target;
+
+mod other {
+    pub trait Greet {
+        fn greet(&self) -> ::std::string::String;
+    }
+}
+
+#[target(alternative = crate::other::Greet, complete)]
+pub trait Greet {
+    fn greet(&self) -> ::std::string::String;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use newer_type::Implement;
+
+#[derive(Implement)]
+#[implement(transparent)]
+#[repr(C)]
+pub struct Name(String);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use newer_type::{implement, target};
+
+#[target]
+trait Numbers {
+    fn numbers(&self) -> impl ::core::iter::IntoIterator<Item = ::core::primitive::u32>;
+}
+
+impl Numbers for Vec<u32> {
+    fn numbers(&self) -> impl IntoIterator<Item = u32> {
+        self.clone()
+    }
+}
+
+#[implement(Numbers)]
+enum AnyNumbers {
+    A(Vec<u32>),
+    B(Vec<u32>),
+}
+
+fn main() {}
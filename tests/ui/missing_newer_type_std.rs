@@ -0,0 +1,7 @@
+use newer_type::implement;
+
+// `newer_type_std` is not a dependency of this crate.
+#[implement(newer_type_std::fmt::Display)]
+pub struct Number(u32);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use newer_type::{implement, target};
+
+// `Length` is referred relatively, which requires `newer_type::repeater!()`.
+pub struct Length(pub u32);
+
+#[target]
+pub trait Area {
+    fn area(&self) -> Length;
+}
+
+impl Area for u32 {
+    fn area(&self) -> Length {
+        Length(*self * *self)
+    }
+}
+
+#[implement(Area)]
+pub struct Square(u32);
+
+fn main() {}
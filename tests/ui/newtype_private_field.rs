@@ -0,0 +1,19 @@
+use newer_type::Newtype;
+
+mod units {
+    use newer_type::implement;
+
+    // The items exposing the private field are private as well.
+    #[implement(newtype)]
+    pub struct Meters(f64);
+}
+
+fn inner<T: Newtype>(value: T) -> T::Inner {
+    value.into_inner()
+}
+
+fn main() {
+    let meters = units::Meters::new(1.0);
+    let _ = meters.as_inner();
+    let _ = inner(meters);
+}
@@ -0,0 +1,41 @@
+// The bundle declared as a trait alias, which rustc warns about as
+// experimental, though it is accepted on stable.
+use newer_type::{implement, target, target_bundle};
+
+newer_type::repeater!();
+
+#[target]
+pub trait Name {
+    fn name(&self) -> ::std::string::String;
+}
+
+#[target]
+pub trait Scale<T> {
+    fn scale(&self, factor: T) -> T;
+}
+
+#[target_bundle]
+pub trait Aliased<T> = crate::Name + crate::Scale<T>;
+
+pub struct Person(u32);
+
+impl Name for Person {
+    fn name(&self) -> String {
+        format!("person{}", self.0)
+    }
+}
+
+impl Scale<u32> for Person {
+    fn scale(&self, factor: u32) -> u32 {
+        self.0 * factor
+    }
+}
+
+#[implement(Aliased<u32>)]
+pub struct Manager(Person);
+
+fn main() {
+    let manager = Manager(Person(4));
+    assert_eq!(manager.name(), "person4");
+    assert_eq!(manager.scale(2), 8);
+}
@@ -0,0 +1,55 @@
+use newer_type::{implement, target};
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+mod animals {
+    use newer_type::target;
+
+    // Named after the trait above, to see that the files do not collide.
+    #[target]
+    pub trait Named {
+        fn species(&self) -> ::std::string::String;
+    }
+}
+
+pub struct Cat;
+
+impl Named for Cat {
+    fn name(&self) -> String {
+        "cat".to_owned()
+    }
+}
+
+impl animals::Named for Cat {
+    fn species(&self) -> String {
+        "felis catus".to_owned()
+    }
+}
+
+#[implement(Named, animals::Named, debug)]
+pub struct Pet(Cat);
+
+fn read_expansion(file: &str) -> String {
+    // trybuild sets `CARGO_TARGET_DIR`, under which the expansion is written.
+    let dir = concat!(
+        env!("CARGO_TARGET_DIR"),
+        "/newer-type/",
+        env!("CARGO_CRATE_NAME")
+    );
+    std::fs::read_to_string(format!("{dir}/{file}")).unwrap()
+}
+
+fn main() {
+    use animals::Named as _;
+    assert_eq!(Pet(Cat).name(), "cat");
+    assert_eq!(Pet(Cat).species(), "felis catus");
+    let code = read_expansion("Pet-Named.rs");
+    assert!(code.starts_with("// Expansion of `#[implement(Named)]` for `Pet`"));
+    // Formatted with one item per line.
+    assert!(code.lines().any(|line| line.trim_start().starts_with("fn name(")));
+    let code = read_expansion("Pet-animals.Named.rs");
+    assert!(code.contains("fn species("));
+}
@@ -0,0 +1,12 @@
+use newer_type::implement;
+
+// A trait without `#[target]` has no companion macro, which is reported along
+// with a warning to add `#[target]`.
+pub trait Plain {
+    fn plain(&self) -> u32;
+}
+
+#[implement(Plain)]
+pub struct Number(u32);
+
+fn main() {}
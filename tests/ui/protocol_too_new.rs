@@ -0,0 +1,19 @@
+use newer_type::target;
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+impl Named for String {
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+pub struct Wrapper(String);
+
+// The tokens passed by `#[implement]` of an incompatible protocol version.
+Named! { version = 3, (Named) pub struct Wrapper(String); }
+
+fn main() {}
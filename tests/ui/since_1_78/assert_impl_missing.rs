@@ -0,0 +1,24 @@
+use newer_type::{implement, target};
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+pub struct Cat;
+
+impl Named for Cat {
+    fn name(&self) -> String {
+        "cat".to_owned()
+    }
+}
+
+pub struct Stone;
+
+#[implement(Named, assert_impl)]
+pub enum Thing {
+    Cat(Cat),
+    Stone { stone: Stone },
+}
+
+fn main() {}
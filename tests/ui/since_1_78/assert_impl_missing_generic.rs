@@ -0,0 +1,17 @@
+use newer_type::{implement, target};
+
+#[target]
+pub trait Named {
+    fn name(&self) -> ::std::string::String;
+}
+
+pub struct Stone;
+
+#[implement]
+pub struct Labeled<T> {
+    #[implement(Named, assert_impl)]
+    stone: Stone,
+    label: T,
+}
+
+fn main() {}
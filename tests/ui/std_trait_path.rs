@@ -0,0 +1,6 @@
+use newer_type::implement;
+
+#[implement(std::fmt::Display)]
+pub struct Name(String);
+
+fn main() {}
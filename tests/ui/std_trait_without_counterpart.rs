@@ -0,0 +1,6 @@
+use newer_type::implement;
+
+#[implement(core::any::Any)]
+pub struct Name(String);
+
+fn main() {}
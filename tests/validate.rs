@@ -0,0 +1,95 @@
+use newer_type::{implement, target, ValidationError};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+newer_type::repeater!();
+
+#[target]
+trait Counter {
+    fn get(&self) -> i32;
+    fn add(&mut self, n: i32);
+}
+
+impl Counter for i32 {
+    fn get(&self) -> i32 {
+        *self
+    }
+
+    fn add(&mut self, n: i32) {
+        *self += n;
+    }
+}
+
+fn is_positive(n: &i32) -> bool {
+    *n > 0
+}
+
+// 1. Constructors of validated newtype
+#[implement(Counter, validate = is_positive)]
+#[derive(Debug, PartialEq)]
+struct Positive(i32);
+
+#[test]
+fn test_validated_new() {
+    assert_eq!(Positive::new(3).unwrap().get(), 3);
+    assert_eq!(Positive::new(-3).unwrap_err().into_inner(), -3);
+    assert_eq!(Positive::try_from(5), Ok(Positive(5)));
+    assert_eq!(Positive::try_from(0), Err(ValidationError::new(0)));
+}
+
+#[test]
+fn test_validated_panic() {
+    let mut value = Positive::new(3).unwrap();
+    value.add(2);
+    assert_eq!(value.get(), 5);
+    let result = std::panic::catch_unwind(move || value.add(-10));
+    assert!(result.is_err());
+}
+
+// 2. Rollback on invalid mutation
+#[implement(Counter, validate = is_positive, on_invalid = "rollback")]
+struct RollbackPositive(i32);
+
+#[test]
+fn test_validated_rollback() {
+    let mut value = RollbackPositive::new(3).unwrap();
+    value.add(-10);
+    assert_eq!(value.get(), 3);
+    value.add(4);
+    assert_eq!(value.get(), 7);
+}
+
+// 3. Other fields are initialized with `Default`
+#[implement(validate = is_positive, on_invalid = "debug_assert")]
+#[implement(Counter)]
+struct TaggedPositive<T> {
+    value: i32,
+    _marker: PhantomData<T>,
+}
+
+#[test]
+fn test_validated_named_struct() {
+    let mut value = TaggedPositive::<String>::new(1).unwrap();
+    value.add(1);
+    assert_eq!(value.get(), 2);
+    assert!(TaggedPositive::<String>::try_from(-1).is_err());
+}
+
+// 4. Generic inner type, without `TryFrom` conflicting with the blanket one
+fn is_non_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value != T::default()
+}
+
+mod export {
+    pub use newer_type;
+}
+
+#[implement(validate = is_non_default, newer_type = crate::export::newer_type)]
+#[derive(Debug)]
+struct NonDefault<T: Default + PartialEq>(T);
+
+#[test]
+fn test_validated_generic() {
+    assert_eq!(NonDefault::new(3).unwrap().0, 3);
+    assert_eq!(NonDefault::new(String::new()).unwrap_err().into_inner(), "");
+}
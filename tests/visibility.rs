@@ -0,0 +1,48 @@
+use newer_type::implement;
+
+mod shapes {
+    use newer_type::{implement, target};
+
+    #[target]
+    pub(crate) trait Area {
+        fn area(&self) -> ::core::primitive::f64;
+    }
+
+    #[target]
+    trait Perimeter {
+        fn perimeter(&self) -> ::core::primitive::f64;
+    }
+
+    pub struct Square(pub f64);
+
+    impl Area for Square {
+        fn area(&self) -> f64 {
+            self.0 * self.0
+        }
+    }
+
+    impl Perimeter for Square {
+        fn perimeter(&self) -> f64 {
+            self.0 * 4.0
+        }
+    }
+
+    #[implement(Perimeter)]
+    pub struct Tile(pub Square);
+
+    impl Tile {
+        pub fn border(&self) -> f64 {
+            self.perimeter()
+        }
+    }
+}
+
+#[implement(shapes::Area)]
+pub struct Room(shapes::Square);
+
+#[test]
+fn test_restricted_visibility() {
+    use shapes::Area;
+    assert_eq!(Room(shapes::Square(3.0)).area(), 9.0);
+    assert_eq!(shapes::Tile(shapes::Square(2.0)).border(), 8.0);
+}